@@ -0,0 +1,59 @@
+use std::fmt;
+
+use crate::grid::Grid;
+
+/// The result of solving one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    /// A picture drawn on a screen, such as the CRT on day 10. Lit pixels
+    /// are `true`.
+    Bitmap(Grid<bool>),
+}
+
+impl Answer {
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Self::Bitmap(_))
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Self::Number(n.try_into().expect("answer does not fit in an i64"))
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(n: isize) -> Self {
+        Self::Number(n.try_into().expect("answer does not fit in an i64"))
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Self::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Self::Text(s.to_owned())
+    }
+}
+
+impl From<Grid<bool>> for Answer {
+    fn from(bitmap: Grid<bool>) -> Self {
+        Self::Bitmap(bitmap)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "{}", n),
+            Self::Text(s) => write!(f, "{}", s),
+            Self::Bitmap(bitmap) => write!(f, "{}", bitmap),
+        }
+    }
+}
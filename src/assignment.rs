@@ -1,3 +1,5 @@
+use std::error;
+use std::fmt;
 use std::ops::RangeInclusive;
 use std::str::FromStr;
 
@@ -6,6 +8,18 @@ pub enum ParseError {
     MissingComma, MissingDash, NotANumber
 }
 
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingComma => write!(f, "expected two ranges separated by a comma"),
+            Self::MissingDash => write!(f, "expected a range of the form start-end"),
+            Self::NotANumber => write!(f, "range bound is not a number"),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
 pub struct Pair(RangeInclusive<usize>, RangeInclusive<usize>);
 
 impl Pair {
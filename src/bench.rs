@@ -0,0 +1,148 @@
+use std::time::{Duration, Instant};
+
+use crate::answer::Answer;
+use crate::days::{Part, Solution};
+use crate::error::Result;
+
+pub const DEFAULT_RUNS: usize = 20;
+
+/// How long one run of a solution spent parsing its input and solving.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Phases {
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+/// Runs `parse` and then `solve`, timing each phase separately.
+pub fn phases<P, F, S>(input: &str, parse: F, solve: S) -> Result<(Answer, Phases)>
+where
+    F: Fn(&str) -> Result<P>,
+    S: Fn(&P) -> Result<Answer>,
+{
+    let start = Instant::now();
+    let parsed = parse(input)?;
+    let parsed_at = Instant::now();
+    let answer = solve(&parsed)?;
+    let phases = Phases {
+        parse: parsed_at - start,
+        solve: parsed_at.elapsed(),
+    };
+    Ok((answer, phases))
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarises a set of timings, or returns None if there are none. An
+    /// even number of samples takes the lower of the two middle values, so
+    /// the median is always a time that was actually measured.
+    pub fn new(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        Some(Self {
+            min: *sorted.first()?,
+            median: sorted[(sorted.len() - 1) / 2],
+            max: *sorted.last()?,
+        })
+    }
+
+    fn to_json(self) -> String {
+        format!(
+            "{{\"min\": {}, \"median\": {}, \"max\": {}}}",
+            self.min.as_nanos(), self.median.as_nanos(), self.max.as_nanos(),
+        )
+    }
+}
+
+/// The timings for one day and part over a number of runs.
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub day: usize,
+    pub part: Part,
+    pub runs: usize,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+impl Record {
+    /// Writes the record as a single line of JSON, with times in
+    /// nanoseconds.
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"day\": {}, \"part\": \"{}\", \"runs\": {}, \"parse_ns\": {}, \"solve_ns\": {}}}",
+            self.day, self.part, self.runs, self.parse.to_json(), self.solve.to_json(),
+        )
+    }
+}
+
+/// Writes records as a JSON array with one record per line, so that saved
+/// runs can be compared with a plain line diff.
+pub fn to_json(records: &[Record]) -> String {
+    let lines: Vec<String> = records.iter().map(|record| format!("  {}", record.to_json())).collect();
+    if lines.is_empty() {
+        return "[]\n".to_string();
+    }
+    format!("[\n{}\n]\n", lines.join(",\n"))
+}
+
+/// Times a solution over `runs` runs, after one untimed warm-up run. Fails
+/// on the first error, since a solution that fails has nothing to measure.
+pub fn bench(solution: &Solution, input: &str, runs: usize) -> Result<Record> {
+    (solution.phases)(input)?;
+    let mut parse = Vec::with_capacity(runs);
+    let mut solve = Vec::with_capacity(runs);
+    for _ in 0..runs.max(1) {
+        let (_, phases) = (solution.phases)(input)?;
+        parse.push(phases.parse);
+        solve.push(phases.solve);
+    }
+    Ok(Record {
+        day: solution.day,
+        part: solution.part,
+        runs: parse.len(),
+        parse: Stats::new(&parse).expect("at least one run"),
+        solve: Stats::new(&solve).expect("at least one run"),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::days::Part;
+    use super::{to_json, Record, Stats};
+
+    fn ns(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&n| Duration::from_nanos(n)).collect()
+    }
+
+    #[test]
+    fn stats() {
+        assert_eq!(Stats::new(&[]), None);
+        let stats = Stats::new(&ns(&[30, 10, 20, 40])).unwrap();
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.median, Duration::from_nanos(20));
+        assert_eq!(stats.max, Duration::from_nanos(40));
+    }
+
+    #[test]
+    fn json_has_one_record_per_line() {
+        let stats = Stats::new(&ns(&[1, 2, 3])).unwrap();
+        let record = Record { day: 1, part: Part::B, runs: 3, parse: stats, solve: stats };
+        assert_eq!(
+            to_json(&[record.clone(), record]),
+            "[\n  {\"day\": 1, \"part\": \"b\", \"runs\": 3, \
+             \"parse_ns\": {\"min\": 1, \"median\": 2, \"max\": 3}, \
+             \"solve_ns\": {\"min\": 1, \"median\": 2, \"max\": 3}},\n  \
+             {\"day\": 1, \"part\": \"b\", \"runs\": 3, \
+             \"parse_ns\": {\"min\": 1, \"median\": 2, \"max\": 3}, \
+             \"solve_ns\": {\"min\": 1, \"median\": 2, \"max\": 3}}\n]\n",
+        );
+        assert_eq!(to_json(&[]), "[]\n");
+    }
+}
@@ -1,5 +1,6 @@
+use aoc::days::day01;
+
 fn main() {
-    let elves = aoc::read_uint_lists(&aoc::input_arg());
-    let solution = elves.iter().map(|ns| ns.iter().sum::<usize>()).max();
-    println!("The elf with the most calories has {}", solution.expect("no input"));
+    let input = aoc::or_exit(aoc::input::try_read_input(&aoc::day_input_arg(1)));
+    println!("The elf with the most calories has {}", aoc::or_exit(day01::part1(&input)));
 }
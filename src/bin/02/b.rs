@@ -1,8 +1,6 @@
-use aoc::rps::{ParseError, Rule};
+use aoc::days::day02;
 
 fn main() {
-    let rules: Vec<Rule> = aoc::read_lines(&aoc::input_arg())
-        .map(|s| s.parse()).collect::<Result<_, ParseError>>().expect("Failed to parse rules");
-    let score: usize = rules.iter().map(Rule::score).sum();
-    println!("The score is {}", score);
+    let input = aoc::or_exit(aoc::input::try_read_input(&aoc::day_input_arg(2)));
+    println!("The score is {}", aoc::or_exit(day02::part2(&input)));
 }
@@ -1,8 +1,6 @@
-use aoc::rucksack::Rucksack;
+use aoc::days::day03;
 
 fn main() {
-    let rucksacks = aoc::read_lines(&aoc::input_arg())
-        .map(|s| Rucksack::new(&s));
-    let priorities: usize = rucksacks.map(|r| r.duplicate_priority()).sum();
-    println!("The sum of the duplicate priorities is {}", priorities);
+    let input = aoc::or_exit(aoc::input::try_read_input(&aoc::day_input_arg(3)));
+    println!("The sum of the duplicate priorities is {}", aoc::or_exit(day03::part1(&input)));
 }
@@ -1,18 +1,6 @@
-use aoc::rucksack::Rucksack;
+use aoc::days::day03;
 
 fn main() {
-    let rucksacks: Vec<Rucksack> = aoc::read_lines(&aoc::input_arg())
-        .map(|s| Rucksack::new(&s)).collect();
-    let mut badges: Vec<char> = Vec::new();
-    let mut i = rucksacks.iter();
-    while let Some(r1) = i.next() {
-        let r2 = i.next().expect("Found one rucksack by itself");
-        let r3 = i.next().expect("Found two rucksacks by themselves");
-        let cs = r1.common(r2);
-        let c = r3.common_chars(&cs);
-        assert_eq!(c.len(), 1, "A group did not have exactly one item in common");
-        badges.push(c[0]);
-    }
-    let priorities: usize = badges.iter().map(|c| Rucksack::priority(*c)).sum();
-    println!("The sum of priorities is {}", priorities);
-}
\ No newline at end of file
+    let input = aoc::or_exit(aoc::input::try_read_input(&aoc::day_input_arg(3)));
+    println!("The sum of priorities is {}", aoc::or_exit(day03::part2(&input)));
+}
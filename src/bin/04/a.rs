@@ -1,8 +1,6 @@
-use aoc::assignment::Pair;
+use aoc::days::day04;
 
 fn main() {
-    let full_overlaps: usize = aoc::read_lines(&aoc::input_arg()).map(|line| {
-        line.parse::<Pair>().expect("Invalid assignment pair")
-    }).map(|pair| if pair.fully_overlaps() { 1 } else { 0 }).sum();
-    println!("The number of full overlaps is {}", full_overlaps);
+    let input = aoc::or_exit(aoc::input::try_read_input(&aoc::day_input_arg(4)));
+    println!("The number of full overlaps is {}", aoc::or_exit(day04::part1(&input)));
 }
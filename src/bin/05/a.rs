@@ -1,26 +1,6 @@
-use std::str::FromStr;
+use aoc::days::day05;
 
 fn main() {
-    let mut lines = aoc::read_lines(&aoc::input_arg());
-    let mut layout: Vec<String> = Vec::new();
-    for line in lines.by_ref() {
-        if line.is_empty() {
-            break;
-        }
-        layout.push(line);
-    }
-    let number_line = layout.pop().expect("did not find stack numbering");
-    let numbers = number_line.split_whitespace()
-        .map(|s| s.parse::<usize>().expect("non-number found in stack numbering"));
-    let mut stacks = aoc::crane::Stacks::new(numbers.count());
-    for row in layout.iter().rev() {
-        stacks.populate(row);
-    }
-    let movements = lines.map(|s| {
-        aoc::crane::Movement::from_str(&s).expect("failed to parse movement")
-    });
-    for movement in movements {
-        stacks.apply(&movement)
-    }
-    println!("The tops of the stacks spell {}", stacks.peek_all());
+    let input = aoc::or_exit(aoc::input::try_read_input(&aoc::day_input_arg(5)));
+    println!("The tops of the stacks spell {}", aoc::or_exit(day05::part1(&input)));
 }
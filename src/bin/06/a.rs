@@ -1,11 +1,6 @@
-use aoc::comms::Datastream;
+use aoc::days::day06;
 
 fn main() {
-    let input = aoc::read_line(&aoc::input_arg());
-    let stream = Datastream::new(&input);
-    if let Some(i) = stream.start_packet_offset() {
-        println!("The packet starts at offset {}", i);
-    } else {
-        println!("No packet start found");
-    }
-}
\ No newline at end of file
+    let input = aoc::or_exit(aoc::input::try_read_input(&aoc::day_input_arg(6)));
+    println!("The packet starts at offset {}", aoc::or_exit(day06::part1(&input)));
+}
@@ -1,11 +1,6 @@
-use aoc::snake::{Movement, Snake};
+use aoc::days::day09;
 
 fn main() {
-    let moves: Result<Vec<Movement>, _> = aoc::read_lines(&aoc::input_arg())
-        .map(|line| line.parse::<Movement>()).collect();
-    let moves = moves.expect("failed to parse moves");
-    let mut snake = Snake::default();
-    snake.apply_moves(&moves);
-    let visited = snake.tails_visited[0].len();
-    println!("The tail visited {} locations", visited);
+    let input = aoc::or_exit(aoc::input::try_read_input(&aoc::day_input_arg(9)));
+    println!("The tail visited {} locations", aoc::or_exit(day09::part1(&input)));
 }
@@ -1,21 +1,6 @@
-use aoc::cpu::{CPU, Instruction};
+use aoc::days::day10;
 
 fn main() {
-    let insts = aoc::read_lines(&aoc::input_arg()).map(|line| line.parse())
-        .collect::<Result<Vec<Instruction>, _>>().expect("failed to parse instructions");
-    let mut cpu = CPU::new(&insts);
-    let mut signals: Vec<isize> = Vec::new();
-    let mut succeeded = true;
-    while succeeded {
-        let cycle = cpu.cycle();
-        if cycle >= 20 && (cycle - 20) % 40 == 0 {
-            signals.push(cpu.signal_strength());
-            if cycle == 220 {
-                break;
-            }
-        }
-        succeeded = cpu.tick();
-    }
-    let sum: isize = signals.iter().take(6).sum();
-    println!("The sum of signal strengths is {}", sum);
+    let input = aoc::or_exit(aoc::input::try_read_input(&aoc::day_input_arg(10)));
+    println!("The sum of signal strengths is {}", aoc::or_exit(day10::part1(&input)));
 }
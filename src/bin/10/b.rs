@@ -1,26 +1,6 @@
-use aoc::cpu::{CPU, Instruction};
+use aoc::days::day10;
 
 fn main() {
-    let insts = aoc::read_lines(&aoc::input_arg()).map(|line| line.parse())
-        .collect::<Result<Vec<Instruction>, _>>().expect("failed to parse instructions");
-    let mut cpu = CPU::new(&insts);
-    let mut succeeded = true;
-    let mut output = String::new();
-    while succeeded {
-        let cycle = ((cpu.cycle() - 1) % 40) as isize;
-        let x = cpu.x();
-        if (cycle-1..=cycle+1).contains(&x) {
-            output.push('#');
-        } else {
-            output.push('.');
-        }
-        if cpu.cycle() % 40 == 0 {
-            output.push('\n');
-            if cycle == 220 {
-                break;
-            }
-        }
-        succeeded = cpu.tick();
-    }
-    println!("The output is:\n{}", &output);
+    let input = aoc::or_exit(aoc::input::try_read_input(&aoc::day_input_arg(10)));
+    println!("The output is:\n{}", aoc::or_exit(day10::part2(&input)));
 }
@@ -1,12 +1,6 @@
-use aoc::monkey::Monkeys;
+use aoc::days::day11;
 
 fn main() {
-    let lines = aoc::read_lines(&aoc::input_arg()).collect::<Vec<_>>().join("\n");
-    let mut monkeys: Monkeys = lines.parse().expect("Failed to parse monkeys");
-    (0..20).for_each(|_| monkeys.do_round());
-    let mut inspections = monkeys.inspections();
-    inspections.sort_unstable();
-    let n1 = inspections.pop().expect("No monkey scores");
-    let n2 = inspections.pop().expect("Only one monkey score");
-    println!("The level of monkey business is {}", n1 * n2);
+    let input = aoc::or_exit(aoc::input::try_read_input(&aoc::day_input_arg(11)));
+    println!("The level of monkey business is {}", aoc::or_exit(day11::part1(&input)));
 }
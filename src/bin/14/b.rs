@@ -1,17 +1,6 @@
-use aoc::cave::{Cave, Movement, Path};
+use aoc::days::day14;
 
 fn main() {
-    let paths: Vec<Path> = aoc::read_lines(&aoc::input_arg())
-        .map(|line| line.parse::<Path>())
-        .collect::<Result<Vec<_>, _>>().expect("Failed to parse paths");
-    let mut cave = Cave::new(paths, false);
-    let mut count: usize = 0;
-    loop {
-        match cave.tick() {
-            Movement::Escape => { count += 1; break; },
-            Movement::Stop => count += 1,
-            _ => {},
-        }
-    }
-    println!("There are {} units of sand at rest", count);
+    let input = aoc::or_exit(aoc::input::try_read_input(&aoc::day_input_arg(14)));
+    println!("There are {} units of sand at rest", aoc::or_exit(day14::part2(&input)));
 }
@@ -0,0 +1,239 @@
+use std::fmt;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Index};
+
+use crate::grid::{Grid, Position};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+// Index has to return a reference, and bits have no address of their own
+static TRUE: bool = true;
+static FALSE: bool = false;
+
+/// A grid of booleans packed 64 cells to a word. Each row starts on a new
+/// word, and the bits past the end of a row are always clear, so whole words
+/// can be counted and combined directly.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    rows: usize,
+    cols: usize,
+    row_words: usize,
+}
+
+impl BitGrid {
+    /// Creates a grid with every cell clear.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let row_words = cols.div_ceil(WORD_BITS);
+        Self {
+            words: vec![0; rows * row_words],
+            rows,
+            cols,
+            row_words,
+        }
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    /// Returns the word holding a cell and the cell's mask within it.
+    fn locate<P: Position>(&self, pos: P) -> (usize, u64) {
+        let (y, x) = pos.row_col();
+        assert!(
+            y < self.rows && x < self.cols,
+            "{:?} is out of bounds for a grid of {:?}", (y, x), self.size(),
+        );
+        (y * self.row_words + x / WORD_BITS, 1 << (x % WORD_BITS))
+    }
+
+    pub fn get<P: Position>(&self, pos: P) -> Option<bool> {
+        let (y, x) = pos.row_col();
+        (y < self.rows && x < self.cols).then(|| self[pos])
+    }
+
+    /// Sets or clears a cell, returning whether it was set before.
+    pub fn set<P: Position>(&mut self, pos: P, value: bool) -> bool {
+        let (word, mask) = self.locate(pos);
+        let was = self.words[word] & mask != 0;
+        if value {
+            self.words[word] |= mask;
+        } else {
+            self.words[word] &= !mask;
+        }
+        was
+    }
+
+    pub fn toggle<P: Position>(&mut self, pos: P) {
+        let (word, mask) = self.locate(pos);
+        self.words[word] ^= mask;
+    }
+
+    /// Returns the number of set cells.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Returns the number of set cells in row `y`.
+    pub fn count_row(&self, y: usize) -> usize {
+        self.row_words(y).iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    fn row_words(&self, y: usize) -> &[u64] {
+        assert!(y < self.rows, "row {} is out of bounds for {} rows", y, self.rows);
+        &self.words[y * self.row_words..(y + 1) * self.row_words]
+    }
+
+    /// Iterates over the columns of the set cells in row `y`, left to right.
+    /// Clear stretches are skipped a word at a time.
+    pub fn row_ones(&self, y: usize) -> impl Iterator<Item = usize> + '_ {
+        self.row_words(y).iter().enumerate().flat_map(|(i, &word)| {
+            let mut bits = word;
+            std::iter::from_fn(move || {
+                if bits == 0 {
+                    return None;
+                }
+                let bit = bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+                Some(i * WORD_BITS + bit)
+            })
+        })
+    }
+
+    /// Iterates over the positions of every set cell in reading order.
+    pub fn ones(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.rows).flat_map(move |y| self.row_ones(y).map(move |x| (y, x)))
+    }
+
+    /// Iterates over every cell in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, bool)> + '_ {
+        (0..self.rows).flat_map(move |y| (0..self.cols).map(move |x| (y, x, self[(y, x)])))
+    }
+
+    fn combine(&mut self, other: &Self, op: impl Fn(u64, u64) -> u64) {
+        assert_eq!(self.size(), other.size(), "grids must be the same size");
+        for (word, &other) in self.words.iter_mut().zip(other.words.iter()) {
+            *word = op(*word, other);
+        }
+    }
+
+    /// Sets every cell set in either grid.
+    pub fn union(&self, other: &Self) -> Self {
+        self | other
+    }
+
+    /// Keeps only the cells set in both grids.
+    pub fn intersection(&self, other: &Self) -> Self {
+        self & other
+    }
+
+    /// Keeps the cells set in exactly one of the grids.
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self ^ other
+    }
+
+    /// Copies the cells into a `Grid<bool>`, for rendering and the like.
+    pub fn to_grid(&self) -> Grid<bool> {
+        Grid::from_fn(self.rows, self.cols, |pos| self[pos])
+    }
+}
+
+impl<P: Position> Index<P> for BitGrid {
+    type Output = bool;
+
+    fn index(&self, pos: P) -> &Self::Output {
+        let (word, mask) = self.locate(pos);
+        if self.words[word] & mask != 0 { &TRUE } else { &FALSE }
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        let (rows, cols) = grid.size();
+        let mut bits = Self::new(rows, cols);
+        for (y, x, &set) in grid.iter() {
+            if set {
+                bits.set((y, x), true);
+            }
+        }
+        bits
+    }
+}
+
+macro_rules! bit_ops {
+    ($($op:ident::$method:ident, $assign:ident::$assign_method:ident, $f:expr;)*) => {
+        $(
+            impl $assign<&BitGrid> for BitGrid {
+                fn $assign_method(&mut self, other: &BitGrid) {
+                    self.combine(other, $f);
+                }
+            }
+
+            impl $op<&BitGrid> for &BitGrid {
+                type Output = BitGrid;
+
+                fn $method(self, other: &BitGrid) -> BitGrid {
+                    let mut result = self.clone();
+                    $assign::$assign_method(&mut result, other);
+                    result
+                }
+            }
+        )*
+    };
+}
+
+bit_ops! {
+    BitOr::bitor, BitOrAssign::bitor_assign, |a, b| a | b;
+    BitAnd::bitand, BitAndAssign::bitand_assign, |a, b| a & b;
+    BitXor::bitxor, BitXorAssign::bitxor_assign, |a, b| a ^ b;
+}
+
+/// Set cells are drawn as `#` and clear cells as `.`, as for `Grid<bool>`.
+impl fmt::Display for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_grid())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::grid::Grid;
+    use crate::point::Point2D;
+    use super::BitGrid;
+
+    #[test]
+    fn set_get_and_count() {
+        // Wide enough that each row spans two words
+        let mut bits = BitGrid::new(3, 70);
+        assert!(!bits.set((1, 0), true));
+        assert!(bits.set((1, 0), true));
+        bits.set((1, 69), true);
+        bits.set((2, 64), true);
+        bits.toggle((0, 5));
+        bits.toggle((2, 64));
+        assert!(bits[(1, 69)] && bits[(0, 5)] && !bits[(2, 64)]);
+        assert_eq!(bits.get((3, 0)), None);
+        assert_eq!(bits.count_ones(), 3);
+        assert_eq!(bits.count_row(1), 2);
+        assert_eq!(bits.row_ones(1).collect::<Vec<_>>(), vec![0, 69]);
+        assert_eq!(bits.ones().collect::<Vec<_>>(), vec![(0, 5), (1, 0), (1, 69)]);
+        let p = Point2D::new(69, 1);
+        assert!(bits[p] && bits.get(p) == Some(true));
+        assert!(bits.set(p, false));
+        bits.toggle(Point2D::new(0, 2));
+        assert_eq!(bits.get(Point2D::new(70, 1)), None);
+        assert_eq!(bits.ones().collect::<Vec<_>>(), vec![(0, 5), (1, 0), (2, 0)]);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = BitGrid::from(&Grid::new_with_cells(vec![true, true, false, false], 2, 2));
+        let b = BitGrid::from(&Grid::new_with_cells(vec![false, true, true, false], 2, 2));
+        assert_eq!(a.union(&b).to_string(), "##\n#.");
+        assert_eq!(a.intersection(&b).to_string(), ".#\n..");
+        assert_eq!(a.symmetric_difference(&b).to_string(), "#.\n#.");
+        let mut c = a.clone();
+        c ^= &a;
+        assert_eq!(c.count_ones(), 0);
+        assert_eq!(BitGrid::from(&a.to_grid()), a);
+    }
+}
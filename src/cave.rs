@@ -1,6 +1,10 @@
+use std::error;
+use std::fmt;
 use std::str::FromStr;
 
+use crate::direction::{Direction8, YAxis};
 use crate::point::Point2D;
+use crate::sparse::SparseGrid;
 
 // For this problem, positive Y is treated as down
 pub type Point = Point2D<isize>;
@@ -22,6 +26,20 @@ pub enum ParseError {
     NotAxisAligned,
 }
 
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotEnoughPoints => write!(f, "a path needs at least two points"),
+            Self::NotEnoughCoords => write!(f, "expected a point of the form x,y"),
+            Self::InvalidNumber => write!(f, "coordinate is not a number"),
+            Self::NotAxisAligned => write!(f, "path segment is neither horizontal nor vertical"),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+#[derive(Clone)]
 pub struct Path {
     points: Vec<Point>,
 }
@@ -65,14 +83,14 @@ impl Path {
                     if p1.y < p2.y {
                         ps.extend((p1.y+1..=p2.y).map(|y| Point::new(p1.x, y)));
                     } else {
-                        ps.extend((p2.y+1..=p1.y).map(|y| Point::new(p1.x, y)));
+                        ps.extend((p2.y..p1.y).rev().map(|y| Point::new(p1.x, y)));
                     }
                 }
                 (_, true) => {
                     ps.extend((p1.x+1..=p2.x).map(|x| Point::new(x, p1.y)));
                 }
                 _ => {
-                    ps.extend((p2.x+1..=p1.x).map(|x| Point::new(x, p1.y)));
+                    ps.extend((p2.x..p1.x).rev().map(|x| Point::new(x, p1.y)));
                 }
             }
         };
@@ -107,9 +125,21 @@ impl FromStr for Path {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Tile {
+    Rock,
+    Sand,
+}
+
+/// Where sand tries to fall, in order of preference.
+const FALLS: [(Direction8, Movement); 3] = [
+    (Direction8::Down, Movement::Down),
+    (Direction8::DownLeft, Movement::DownLeft),
+    (Direction8::DownRight, Movement::DownRight),
+];
+
 pub struct Cave {
-    paths: Vec<Path>,
-    sand: Vec<Point>,
+    tiles: SparseGrid<Tile>,
     cur_sand: Option<Point>,
     floor: isize,
     infinite: bool,
@@ -117,31 +147,31 @@ pub struct Cave {
 
 impl Cave {
     pub fn new(paths: Vec<Path>, infinite: bool) -> Self {
-        let mut floor = 0;
-        for path in paths.iter() {
-            for p in path.points.iter() {
-                if p.y > floor {
-                    floor = p.y
-                }
-            }
-        }
+        let tiles: SparseGrid<Tile> = paths.iter()
+            .flat_map(|path| path.to_points())
+            .map(|p| (p, Tile::Rock))
+            .collect();
+        let mut floor = tiles.bounds().map_or(0, |bounds| bounds.max.y.max(0));
         if !infinite {
             floor += 2;
         }
         Self {
-            paths,
-            sand: Vec::new(),
+            tiles,
             cur_sand: None,
             floor,
             infinite,
         }
     }
 
+    pub fn tiles(&self) -> &SparseGrid<Tile> {
+        &self.tiles
+    }
+
     fn intersects(&self, p: Point) -> bool {
         if !self.infinite && p.y == self.floor {
             true
         } else {
-            self.paths.iter().any(|path| path.contains(p))
+            self.tiles.contains(p)
         }
     }
 
@@ -157,23 +187,14 @@ impl Cave {
             self.cur_sand = None;
             return Movement::Escape;
         }
-        let mut q = p;
-        q.y += 1;
-        if !self.intersects(q) {
-            self.cur_sand = Some(q);
-            return Movement::Down;
-        }
-        q.x -= 1;
-        if !self.intersects(q) {
-            self.cur_sand = Some(q);
-            return Movement::DownLeft;
-        }
-        q.x += 2;
-        if !self.intersects(q) {
-            self.cur_sand = Some(q);
-            return Movement::DownRight;
+        for (dir, movement) in FALLS {
+            let q = p.step(dir, YAxis::Down, 1);
+            if !self.intersects(q) {
+                self.cur_sand = Some(q);
+                return movement;
+            }
         }
-        self.sand.push(p);
+        self.tiles.insert(p, Tile::Sand);
         self.cur_sand = None;
         if !self.infinite && p.x == 500 && p.y == 0 {
             return Movement::Escape;
@@ -181,3 +202,17 @@ impl Cave {
         Movement::Stop
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{Path, Point};
+
+    #[test]
+    fn path_points_in_either_direction() {
+        let path: Path = "498,4 -> 498,6 -> 496,6".parse().unwrap();
+        let points: Vec<(isize, isize)> = path.to_points().iter().map(|p| (p.x, p.y)).collect();
+        assert_eq!(points, vec![(498, 4), (498, 5), (498, 6), (497, 6), (496, 6)]);
+        let path: Path = "5,3 -> 5,1".parse().unwrap();
+        assert_eq!(path.to_points(), vec![Point::new(5, 3), Point::new(5, 2), Point::new(5, 1)]);
+    }
+}
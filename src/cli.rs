@@ -1,5 +1,27 @@
+use std::error;
+use std::fmt;
+
 use regex::Regex;
 
+#[derive(Debug, Copy, Clone)]
+pub enum ParseError {
+    NoDirectory,
+    InvalidSize,
+    UnrecognizedItem,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoDirectory => write!(f, "no directory at the current path"),
+            Self::InvalidSize => write!(f, "file size is not a number"),
+            Self::UnrecognizedItem => write!(f, "item is not a file or directory"),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
 pub enum Node {
     File(File),
     Directory(Directory),
@@ -179,6 +201,10 @@ impl Cli {
         };
     }
 
+    pub fn root(&self) -> &Node {
+        &self.root
+    }
+
     pub fn root_dir(&mut self) -> &mut Node {
         &mut self.root
     }
@@ -200,28 +226,30 @@ impl Cli {
         Some(dir)
     }
 
-    pub fn add_item(&mut self, item: &str) {
-        let parent = self.cur_dir_mut().expect("no directory at the current path");
+    pub fn add_item(&mut self, item: &str) -> Result<(), ParseError> {
+        let parent = self.cur_dir_mut().ok_or(ParseError::NoDirectory)?;
         let dir_re = Regex::new(r"dir (\w+)").expect("failed to build regex");
         let file_re = Regex::new(r"(\d+) (.*)").expect("failed to build regex");
         if let Some(caps) = dir_re.captures(item) {
             let name = &caps[1];
             parent.mkdir(name);
         } else if let Some(caps) = file_re.captures(item) {
-            let size = &caps[1].parse().expect("file size is not a number");
+            let size = caps[1].parse().map_err(|_| ParseError::InvalidSize)?;
             let name = &caps[2];
-            parent.mkfile(name, *size);
+            parent.mkfile(name, size);
         } else {
-            panic!("item is not a file or dirextory");
+            return Err(ParseError::UnrecognizedItem);
         }
+        Ok(())
     }
 
-    pub fn handle_line(&mut self, line: &str) {
+    pub fn handle_line(&mut self, line: &str) -> Result<(), ParseError> {
         if let Some(dir) = line.strip_prefix("$ cd ") {
             self.cd(dir);
         } else if line != "$ ls" {
-            self.add_item(line);
+            self.add_item(line)?;
         }
+        Ok(())
     }
 }
 
@@ -1,3 +1,5 @@
+use std::error;
+use std::fmt;
 use std::str::FromStr;
 
 #[derive(Debug, Copy, Clone)]
@@ -6,6 +8,17 @@ pub enum ParseInstructionError {
     InvalidParameter,
 }
 
+impl fmt::Display for ParseInstructionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidInstruction => write!(f, "unknown instruction"),
+            Self::InvalidParameter => write!(f, "missing or invalid instruction parameter"),
+        }
+    }
+}
+
+impl error::Error for ParseInstructionError {}
+
 #[derive(Debug, Copy, Clone)]
 pub enum Instruction {
     Noop,
@@ -1,10 +1,25 @@
+use std::error;
+use std::fmt;
 use std::str::FromStr;
 
 #[derive(Debug, Copy, Clone)]
 pub enum ParseError {
     InvalidMovement,
+    /// A movement names a stack that the drawing does not have.
+    NoSuchStack(usize),
 }
 
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMovement => write!(f, "expected a movement of the form \"move N from A to B\""),
+            Self::NoSuchStack(n) => write!(f, "there is no stack {}", n),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
 pub struct Movement {
     count: usize,
     from: usize,
@@ -31,6 +46,7 @@ impl FromStr for Movement {
     }
 }
 
+#[derive(Clone)]
 pub struct Stack {
     crates: Vec<char>,
 }
@@ -55,6 +71,7 @@ impl Default for Stack {
     }
 }
 
+#[derive(Clone)]
 pub struct Stacks {
     stacks: Vec<Stack>,
 }
@@ -66,6 +83,15 @@ impl Stacks {
         Stacks { stacks }
     }
 
+    /// Checks that a movement only names stacks that exist, so applying it
+    /// cannot index out of bounds.
+    pub fn check(&self, movement: &Movement) -> Result<(), ParseError> {
+        match [movement.from, movement.to].into_iter().find(|n| !(1..=self.stacks.len()).contains(n)) {
+            Some(n) => Err(ParseError::NoSuchStack(n)),
+            None => Ok(()),
+        }
+    }
+
     pub fn apply(&mut self, movement: &Movement) {
         for _ in 0..movement.count {
             let c = self.stacks[movement.from - 1].pop();
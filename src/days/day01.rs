@@ -0,0 +1,33 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::input::try_parse_uint_lists;
+
+/// Returns the total calories carried by each elf.
+pub fn parse(input: &str) -> Result<Vec<usize>> {
+    let elves = try_parse_uint_lists(input)?;
+    Ok(elves.iter().map(|ns| ns.iter().sum()).collect())
+}
+
+pub fn solve1(calories: &[usize]) -> Result<Answer> {
+    let solution = calories.iter().max()
+        .ok_or_else(|| Error::no_solution("no elves"))?;
+    Ok((*solution).into())
+}
+
+pub fn solve2(calories: &[usize]) -> Result<Answer> {
+    if calories.len() < 3 {
+        return Err(Error::no_solution("fewer than three elves"));
+    }
+    let mut ranking = calories.to_vec();
+    ranking.sort_unstable();
+    let top: usize = ranking.iter().rev().take(3).sum();
+    Ok(top.into())
+}
+
+pub fn part1(input: &str) -> Result<Answer> {
+    solve1(&parse(input)?)
+}
+
+pub fn part2(input: &str) -> Result<Answer> {
+    solve2(&parse(input)?)
+}
@@ -0,0 +1,30 @@
+use crate::answer::Answer;
+use crate::error::Result;
+use crate::input::parse_lines;
+use crate::rps::{Round, Rule};
+
+pub fn parse_rounds(input: &str) -> Result<Vec<Round>> {
+    parse_lines(input)
+}
+
+pub fn parse_rules(input: &str) -> Result<Vec<Rule>> {
+    parse_lines(input)
+}
+
+pub fn solve1(rounds: &[Round]) -> Result<Answer> {
+    let score: usize = rounds.iter().map(Round::score).sum();
+    Ok(score.into())
+}
+
+pub fn solve2(rules: &[Rule]) -> Result<Answer> {
+    let score: usize = rules.iter().map(Rule::score).sum();
+    Ok(score.into())
+}
+
+pub fn part1(input: &str) -> Result<Answer> {
+    solve1(&parse_rounds(input)?)
+}
+
+pub fn part2(input: &str) -> Result<Answer> {
+    solve2(&parse_rules(input)?)
+}
@@ -0,0 +1,46 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::rucksack::Rucksack;
+
+pub fn parse(input: &str) -> Result<Vec<Rucksack>> {
+    input.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if line.len() & 1 == 1 {
+                Err(Error::parse("rucksack must have an even number of items").at_line(i + 1))
+            } else {
+                Ok(Rucksack::new(line))
+            }
+        })
+        .collect()
+}
+
+pub fn solve1(rucksacks: &[Rucksack]) -> Result<Answer> {
+    let priorities: usize = rucksacks.iter().map(|r| r.duplicate_priority()).sum();
+    Ok(priorities.into())
+}
+
+pub fn solve2(rucksacks: &[Rucksack]) -> Result<Answer> {
+    let mut badges: Vec<char> = Vec::new();
+    for group in rucksacks.chunks(3) {
+        let [r1, r2, r3] = group else {
+            return Err(Error::no_solution("rucksacks do not divide into groups of three"));
+        };
+        let cs = r1.common(r2);
+        let c = r3.common_chars(&cs);
+        if c.len() != 1 {
+            return Err(Error::no_solution("a group did not have exactly one item in common"));
+        }
+        badges.push(c[0]);
+    }
+    let priorities: usize = badges.iter().map(|c| Rucksack::priority(*c)).sum();
+    Ok(priorities.into())
+}
+
+pub fn part1(input: &str) -> Result<Answer> {
+    solve1(&parse(input)?)
+}
+
+pub fn part2(input: &str) -> Result<Answer> {
+    solve2(&parse(input)?)
+}
@@ -0,0 +1,26 @@
+use crate::answer::Answer;
+use crate::assignment::Pair;
+use crate::error::Result;
+use crate::input::parse_lines;
+
+pub fn parse(input: &str) -> Result<Vec<Pair>> {
+    parse_lines(input)
+}
+
+pub fn solve1(pairs: &[Pair]) -> Result<Answer> {
+    let full_overlaps = pairs.iter().filter(|pair| pair.fully_overlaps()).count();
+    Ok(full_overlaps.into())
+}
+
+pub fn solve2(pairs: &[Pair]) -> Result<Answer> {
+    let overlaps = pairs.iter().filter(|pair| pair.overlaps()).count();
+    Ok(overlaps.into())
+}
+
+pub fn part1(input: &str) -> Result<Answer> {
+    solve1(&parse(input)?)
+}
+
+pub fn part2(input: &str) -> Result<Answer> {
+    solve2(&parse(input)?)
+}
@@ -0,0 +1,54 @@
+use crate::answer::Answer;
+use crate::crane::{Movement, Stacks};
+use crate::error::{Error, Result};
+use crate::input::{blocks, parse_lines};
+
+pub fn parse(input: &str) -> Result<(Stacks, Vec<Movement>)> {
+    let mut blocks = blocks(input);
+    let (_, layout) = blocks.next().ok_or_else(|| Error::parse("expected a stack layout"))?;
+    let (moves_line, moves) = blocks.next()
+        .ok_or_else(|| Error::parse("expected a blank line and moves after the stack layout"))?;
+    let mut layout: Vec<&str> = layout.lines().collect();
+    let number_line = layout.pop().ok_or_else(|| Error::parse("did not find stack numbering"))?;
+    let numbering_line_no = layout.len() + 1;
+    let mut count = 0;
+    for s in number_line.split_whitespace() {
+        s.parse::<usize>().map_err(|_| {
+            Error::parse("non-number found in stack numbering").at_line(numbering_line_no)
+        })?;
+        count += 1;
+    }
+    let mut stacks = Stacks::new(count);
+    for row in layout.iter().rev() {
+        stacks.populate(row);
+    }
+    let movements: Vec<Movement> = parse_lines(moves).map_err(|err| err.shifted(moves_line - 1))?;
+    for (i, movement) in movements.iter().enumerate() {
+        stacks.check(movement).map_err(|err| Error::from(err).at_line(moves_line + i))?;
+    }
+    Ok((stacks, movements))
+}
+
+pub fn solve1((stacks, movements): &(Stacks, Vec<Movement>)) -> Result<Answer> {
+    let mut stacks = stacks.clone();
+    for movement in movements.iter() {
+        stacks.apply(movement)
+    }
+    Ok(stacks.peek_all().into())
+}
+
+pub fn solve2((stacks, movements): &(Stacks, Vec<Movement>)) -> Result<Answer> {
+    let mut stacks = stacks.clone();
+    for movement in movements.iter() {
+        stacks.apply_keeping_order(movement)
+    }
+    Ok(stacks.peek_all().into())
+}
+
+pub fn part1(input: &str) -> Result<Answer> {
+    solve1(&parse(input)?)
+}
+
+pub fn part2(input: &str) -> Result<Answer> {
+    solve2(&parse(input)?)
+}
@@ -0,0 +1,28 @@
+use crate::answer::Answer;
+use crate::comms::Datastream;
+use crate::error::{Error, Result};
+
+pub fn parse(input: &str) -> Result<Datastream> {
+    let line = input.lines().next().ok_or_else(|| Error::parse("no lines of input"))?;
+    Ok(Datastream::new(line))
+}
+
+pub fn solve1(stream: &Datastream) -> Result<Answer> {
+    let offset = stream.start_packet_offset()
+        .ok_or_else(|| Error::no_solution("no packet start found"))?;
+    Ok(offset.into())
+}
+
+pub fn solve2(stream: &Datastream) -> Result<Answer> {
+    let offset = stream.start_message_offset()
+        .ok_or_else(|| Error::no_solution("no message start found"))?;
+    Ok(offset.into())
+}
+
+pub fn part1(input: &str) -> Result<Answer> {
+    solve1(&parse(input)?)
+}
+
+pub fn part2(input: &str) -> Result<Answer> {
+    solve2(&parse(input)?)
+}
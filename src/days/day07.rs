@@ -0,0 +1,57 @@
+use crate::answer::Answer;
+use crate::cli::{Cli, Node};
+use crate::error::{Error, Result};
+
+const MAX_SIZE: usize = 100000;
+const DISK_SIZE: usize = 70000000;
+const FREE_SPACE_REQUIRED: usize = 30000000;
+
+pub fn parse(input: &str) -> Result<Cli> {
+    let mut cli = Cli::new();
+    for (i, line) in input.lines().enumerate() {
+        cli.handle_line(line).map_err(|err| Error::from(err).at_line(i + 1))?;
+    }
+    Ok(cli)
+}
+
+pub fn solve1(cli: &Cli) -> Result<Answer> {
+    let sizes = cli.iter().fold(0, |acc, node| {
+        if let Node::Directory(dir) = node {
+            let size = dir.size();
+            if size <= MAX_SIZE {
+                return acc + size;
+            }
+        };
+        acc
+    });
+    Ok(sizes.into())
+}
+
+pub fn solve2(cli: &Cli) -> Result<Answer> {
+    let free_space = DISK_SIZE.checked_sub(cli.root().size())
+        .ok_or_else(|| Error::no_solution("the files do not fit on the disk"))?;
+    let additional_space_required = match FREE_SPACE_REQUIRED.checked_sub(free_space) {
+        Some(space) => space,
+        None => return Ok(Answer::Number(0)),
+    };
+    let min_size = cli.iter().fold(0, |min, node| {
+        if let Node::Directory(dir) = node {
+            let size = dir.size();
+            if size >= additional_space_required && (min == 0 || size < min) {
+                return size;
+            } else {
+                return min;
+            }
+        };
+        min
+    });
+    Ok(min_size.into())
+}
+
+pub fn part1(input: &str) -> Result<Answer> {
+    solve1(&parse(input)?)
+}
+
+pub fn part2(input: &str) -> Result<Answer> {
+    solve2(&parse(input)?)
+}
@@ -0,0 +1,39 @@
+use crate::answer::Answer;
+use crate::error::Result;
+use crate::grid::{Grid, OFFSETS4};
+use crate::input::try_parse_uint_grid;
+use crate::sight::{viewing_distance, visibility_map};
+
+pub fn parse(input: &str) -> Result<Grid<usize>> {
+    Ok(try_parse_uint_grid(input)?)
+}
+
+pub fn solve1(grid: &Grid<usize>) -> Result<Answer> {
+    let visible = visibility_map(grid).count_ones();
+    Ok(visible.into())
+}
+
+/// Multiplies how far each tree can see in every direction.
+pub fn scenic_scores(grid: &Grid<usize>) -> Grid<usize> {
+    let (rows, cols) = grid.size();
+    let mut scores: Grid<usize> = Grid::new_with_cells(vec![1; rows * cols], rows, cols);
+    for direction in OFFSETS4 {
+        for (y, x, &distance) in viewing_distance(grid, direction).iter() {
+            scores[(y, x)] *= distance;
+        }
+    }
+    scores
+}
+
+pub fn solve2(grid: &Grid<usize>) -> Result<Answer> {
+    let best = scenic_scores(grid).iter().map(|(_, _, &score)| score).max().unwrap_or(0);
+    Ok(best.into())
+}
+
+pub fn part1(input: &str) -> Result<Answer> {
+    solve1(&parse(input)?)
+}
+
+pub fn part2(input: &str) -> Result<Answer> {
+    solve2(&parse(input)?)
+}
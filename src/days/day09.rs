@@ -0,0 +1,31 @@
+use crate::answer::Answer;
+use crate::error::Result;
+use crate::input::parse_lines;
+use crate::point::Point2D;
+use crate::snake::{Movement, Snake};
+
+pub fn parse(input: &str) -> Result<Vec<Movement>> {
+    parse_lines(input)
+}
+
+pub fn solve1(moves: &[Movement]) -> Result<Answer> {
+    let mut snake = Snake::default();
+    snake.apply_moves(moves);
+    let visited = snake.tails_visited[0].len();
+    Ok(visited.into())
+}
+
+pub fn solve2(moves: &[Movement]) -> Result<Answer> {
+    let mut snake = Snake::new(Point2D::default(), 9);
+    snake.apply_moves(moves);
+    let visited = snake.tails_visited[8].len();
+    Ok(visited.into())
+}
+
+pub fn part1(input: &str) -> Result<Answer> {
+    solve1(&parse(input)?)
+}
+
+pub fn part2(input: &str) -> Result<Answer> {
+    solve2(&parse(input)?)
+}
@@ -0,0 +1,56 @@
+use crate::answer::Answer;
+use crate::cpu::{CPU, Instruction};
+use crate::error::Result;
+use crate::grid::Grid;
+use crate::input::parse_lines;
+
+const CRT_ROWS: usize = 6;
+const CRT_COLS: usize = 40;
+
+pub fn parse(input: &str) -> Result<Vec<Instruction>> {
+    parse_lines(input)
+}
+
+pub fn solve1(insts: &[Instruction]) -> Result<Answer> {
+    let mut cpu = CPU::new(insts);
+    let mut signals: Vec<isize> = Vec::new();
+    let mut succeeded = true;
+    while succeeded {
+        let cycle = cpu.cycle();
+        if cycle >= 20 && (cycle - 20).is_multiple_of(40) {
+            signals.push(cpu.signal_strength());
+            if cycle == 220 {
+                break;
+            }
+        }
+        succeeded = cpu.tick();
+    }
+    let sum: isize = signals.iter().take(6).sum();
+    Ok(sum.into())
+}
+
+pub fn solve2(insts: &[Instruction]) -> Result<Answer> {
+    let mut cpu = CPU::new(insts);
+    let mut crt: Grid<bool> = Grid::new(CRT_ROWS, CRT_COLS);
+    loop {
+        let pixel = cpu.cycle() - 1;
+        if pixel >= CRT_ROWS * CRT_COLS {
+            break;
+        }
+        let (row, col) = (pixel / CRT_COLS, pixel % CRT_COLS);
+        let x = cpu.x();
+        crt[(row, col)] = (x - 1..=x + 1).contains(&(col as isize));
+        if !cpu.tick() {
+            break;
+        }
+    }
+    Ok(crt.into())
+}
+
+pub fn part1(input: &str) -> Result<Answer> {
+    solve1(&parse(input)?)
+}
+
+pub fn part2(input: &str) -> Result<Answer> {
+    solve2(&parse(input)?)
+}
@@ -0,0 +1,38 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::input::parse_blocks;
+use crate::monkey::{Monkey, Monkeys};
+
+fn monkey_business(monkeys: &Monkeys) -> Result<usize> {
+    let mut inspections = monkeys.inspections();
+    inspections.sort_unstable();
+    match (inspections.pop(), inspections.pop()) {
+        (Some(n1), Some(n2)) => Ok(n1 * n2),
+        _ => Err(Error::no_solution("fewer than two monkeys")),
+    }
+}
+
+pub fn parse(input: &str) -> Result<Monkeys> {
+    Ok(Monkeys::new(parse_blocks::<Monkey>(input)?))
+}
+
+pub fn solve1(monkeys: &Monkeys) -> Result<Answer> {
+    let mut monkeys = monkeys.clone();
+    (0..20).for_each(|_| monkeys.do_round());
+    Ok(monkey_business(&monkeys)?.into())
+}
+
+pub fn solve2(monkeys: &Monkeys) -> Result<Answer> {
+    let mut monkeys = monkeys.clone();
+    monkeys.set_reduces(false);
+    (0..10000).for_each(|_| monkeys.do_round());
+    Ok(monkey_business(&monkeys)?.into())
+}
+
+pub fn part1(input: &str) -> Result<Answer> {
+    solve1(&parse(input)?)
+}
+
+pub fn part2(input: &str) -> Result<Answer> {
+    solve2(&parse(input)?)
+}
@@ -0,0 +1,50 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::hill::{Coord, Hill};
+
+pub fn parse(input: &str) -> Result<Hill> {
+    Ok(Hill::new(input.parse()?))
+}
+
+fn end(hill: &Hill) -> Result<Coord> {
+    hill.find('E').ok_or_else(|| Error::parse("end position not found"))
+}
+
+pub fn solve1(hill: &Hill) -> Result<Answer> {
+    let start_pos = hill.find('S').ok_or_else(|| Error::parse("start position not found"))?;
+    let path = hill.find_path(start_pos, end(hill)?, 0)
+        .ok_or_else(|| Error::no_solution("no path to the end position"))?;
+
+    Ok(path.len().into())
+}
+
+pub fn solve2(hill: &Hill) -> Result<Answer> {
+    let end_pos = end(hill)?;
+    let starts: Vec<Coord> = hill.grid().iter_points()
+        .filter(|(_, &c)| c == 'S' || c == 'a')
+        .map(|(pos, _)| pos)
+        .collect();
+    let mut min_len: usize = 0;
+    for start_pos in starts {
+        if let Some(path) = hill.find_path(start_pos, end_pos, min_len) {
+            // The path runs backwards from the end, so the first 'a' on it
+            // is de facto a shorter route
+            let plen = path.iter()
+                .position(|&coord| hill.grid()[coord] == 'a')
+                .unwrap_or(path.len());
+            if min_len == 0 || plen < min_len {
+                min_len = plen;
+            }
+        }
+    }
+
+    Ok(min_len.into())
+}
+
+pub fn part1(input: &str) -> Result<Answer> {
+    solve1(&parse(input)?)
+}
+
+pub fn part2(input: &str) -> Result<Answer> {
+    solve2(&parse(input)?)
+}
@@ -0,0 +1,68 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::inttree::UintNode;
+
+pub fn parse(input: &str) -> Result<Vec<UintNode>> {
+    input.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| line.parse::<UintNode>().map_err(|err| Error::from(err).at_line(i + 1)))
+        .collect()
+}
+
+pub fn solve1(nodes: &[UintNode]) -> Result<Answer> {
+    let mut node_iter = nodes.iter();
+    let mut pair_index: usize = 0;
+    let mut good_indices: Vec<usize> = Vec::new();
+    while let Some(left) = node_iter.next() {
+        pair_index += 1;
+        let right = node_iter.next().ok_or_else(|| Error::parse("unbalanced pair"))?;
+        if left.cmp(right) == std::cmp::Ordering::Less {
+            good_indices.push(pair_index);
+        }
+    }
+    let score: usize = good_indices.iter().sum();
+    Ok(score.into())
+}
+
+pub fn solve2(nodes: &[UintNode]) -> Result<Answer> {
+    let dividers: [UintNode; 2] = [
+        "[[2]]".parse().expect("Failed to parse known good input"),
+        "[[6]]".parse().expect("Failed to parse known good input"),
+    ];
+    let mut nodes: Vec<&UintNode> = nodes.iter().chain(dividers.iter()).collect();
+    nodes.sort_unstable();
+    let mut first_index = 0;
+    let mut second_index = 0;
+    for (index, node) in nodes.iter().enumerate() {
+        if let UintNode::Tree(ns) = node {
+            if ns.len() != 1 {
+                continue;
+            }
+            if let UintNode::Tree(ns) = &ns[0] {
+                if ns.len() != 1 {
+                    continue;
+                }
+                if let UintNode::Leaf(n) = &ns[0] {
+                    match *n {
+                        2 => first_index = index + 1,
+                        6 => second_index = index + 1,
+                        _ => {}
+                    }
+                }
+            }
+        }
+        if second_index > 0 {
+            break;
+        }
+    }
+    Ok((first_index * second_index).into())
+}
+
+pub fn part1(input: &str) -> Result<Answer> {
+    solve1(&parse(input)?)
+}
+
+pub fn part2(input: &str) -> Result<Answer> {
+    solve2(&parse(input)?)
+}
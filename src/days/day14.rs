@@ -0,0 +1,42 @@
+use crate::answer::Answer;
+use crate::cave::{Cave, Movement, Path};
+use crate::error::Result;
+use crate::input::parse_lines;
+
+pub fn parse(input: &str) -> Result<Vec<Path>> {
+    parse_lines(input)
+}
+
+pub fn solve1(paths: &[Path]) -> Result<Answer> {
+    let mut cave = Cave::new(paths.to_vec(), true);
+    let mut count: usize = 0;
+    loop {
+        match cave.tick() {
+            Movement::Escape => break,
+            Movement::Stop => count += 1,
+            _ => {},
+        }
+    }
+    Ok(count.into())
+}
+
+pub fn solve2(paths: &[Path]) -> Result<Answer> {
+    let mut cave = Cave::new(paths.to_vec(), false);
+    let mut count: usize = 0;
+    loop {
+        match cave.tick() {
+            Movement::Escape => { count += 1; break; },
+            Movement::Stop => count += 1,
+            _ => {},
+        }
+    }
+    Ok(count.into())
+}
+
+pub fn part1(input: &str) -> Result<Answer> {
+    solve1(&parse(input)?)
+}
+
+pub fn part2(input: &str) -> Result<Answer> {
+    solve2(&parse(input)?)
+}
@@ -0,0 +1,145 @@
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::answer::Answer;
+use crate::bench::{self, Phases};
+use crate::error::{Error, ErrorKind};
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+
+#[derive(Debug, Copy, Clone)]
+pub enum ParsePartError {
+    UnknownPart,
+}
+
+impl fmt::Display for ParsePartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownPart => write!(f, "expected part a or b"),
+        }
+    }
+}
+
+impl error::Error for ParsePartError {}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    A,
+    B,
+}
+
+impl FromStr for Part {
+    type Err = ParsePartError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "a" | "A" | "1" => Ok(Self::A),
+            "b" | "B" | "2" => Ok(Self::B),
+            _ => Err(Self::Err::UnknownPart),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::A => write!(f, "a"),
+            Self::B => write!(f, "b"),
+        }
+    }
+}
+
+/// A solver takes the full text of a puzzle input and returns the answer.
+pub type Solver = fn(&str) -> crate::Result<Answer>;
+
+/// Solves like a [`Solver`], but also reports how long parsing and solving
+/// took on their own.
+pub type PhaseTimer = fn(&str) -> crate::Result<(Answer, Phases)>;
+
+pub struct Solution {
+    pub day: usize,
+    pub part: Part,
+    pub solve: Solver,
+    pub phases: PhaseTimer,
+}
+
+impl Solution {
+    const fn new(day: usize, part: Part, solve: Solver, phases: PhaseTimer) -> Self {
+        Self { day, part, solve, phases }
+    }
+}
+
+/// Registers a part given the day module's entry point, parser and solver.
+macro_rules! solution {
+    ($day:literal, $part:ident, $module:ident::$entry:ident, $parse:ident, $solve:ident) => {
+        Solution::new(
+            $day,
+            Part::$part,
+            $module::$entry,
+            |input| bench::phases(input, $module::$parse, |parsed| $module::$solve(parsed)),
+        )
+    };
+}
+
+/// Every registered solution, ordered by day and then by part. Adding a day
+/// only requires a new module and its entries here.
+pub const SOLUTIONS: &[Solution] = &[
+    solution!(1, A, day01::part1, parse, solve1),
+    solution!(1, B, day01::part2, parse, solve2),
+    solution!(2, A, day02::part1, parse_rounds, solve1),
+    solution!(2, B, day02::part2, parse_rules, solve2),
+    solution!(3, A, day03::part1, parse, solve1),
+    solution!(3, B, day03::part2, parse, solve2),
+    solution!(4, A, day04::part1, parse, solve1),
+    solution!(4, B, day04::part2, parse, solve2),
+    solution!(5, A, day05::part1, parse, solve1),
+    solution!(5, B, day05::part2, parse, solve2),
+    solution!(6, A, day06::part1, parse, solve1),
+    solution!(6, B, day06::part2, parse, solve2),
+    solution!(7, A, day07::part1, parse, solve1),
+    solution!(7, B, day07::part2, parse, solve2),
+    solution!(8, A, day08::part1, parse, solve1),
+    solution!(8, B, day08::part2, parse, solve2),
+    solution!(9, A, day09::part1, parse, solve1),
+    solution!(9, B, day09::part2, parse, solve2),
+    solution!(10, A, day10::part1, parse, solve1),
+    solution!(10, B, day10::part2, parse, solve2),
+    solution!(11, A, day11::part1, parse, solve1),
+    solution!(11, B, day11::part2, parse, solve2),
+    solution!(12, A, day12::part1, parse, solve1),
+    solution!(12, B, day12::part2, parse, solve2),
+    solution!(13, A, day13::part1, parse, solve1),
+    solution!(13, B, day13::part2, parse, solve2),
+    solution!(14, A, day14::part1, parse, solve1),
+    solution!(14, B, day14::part2, parse, solve2),
+];
+
+pub fn find(day: usize, part: Part) -> Option<&'static Solution> {
+    SOLUTIONS.iter().find(|s| s.day == day && s.part == part)
+}
+
+/// Looks up and runs the solution for a day and part.
+pub fn solve(day: usize, part: Part, input: &str) -> crate::Result<Answer> {
+    let solution = find(day, part).ok_or_else(|| {
+        Error::new(ErrorKind::Unregistered, format!("no solution registered for day {} part {}", day, part))
+    })?;
+    (solution.solve)(input)
+}
+
+pub fn for_day(day: usize) -> impl Iterator<Item = &'static Solution> {
+    SOLUTIONS.iter().filter(move |s| s.day == day)
+}
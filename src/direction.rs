@@ -0,0 +1,229 @@
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::point::Point2D;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParseDirectionError {
+    UnknownDirection,
+}
+
+impl fmt::Display for ParseDirectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownDirection => write!(f, "expected a direction such as U, N or ^"),
+        }
+    }
+}
+
+impl error::Error for ParseDirectionError {}
+
+/// Which way y increases. Grids and screens count rows downwards, while
+/// puzzles drawn on graph paper often count upwards.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum YAxis {
+    #[default]
+    Down,
+    Up,
+}
+
+/// A direction that can be turned into a unit step.
+pub trait Heading: Copy {
+    /// Returns the step one cell in this direction, under the given y-axis
+    /// convention.
+    fn offset(self, axis: YAxis) -> Point2D<isize>;
+
+    /// Returns the step as `(dy, dx)`, for the [`Grid`](crate::grid::Grid)
+    /// methods that take offsets. Grid rows always count downwards.
+    fn grid_offset(self) -> (isize, isize) {
+        let p = self.offset(YAxis::Down);
+        (p.y, p.x)
+    }
+}
+
+/// Flips a y-down offset for the given convention.
+fn oriented(dx: isize, dy: isize, axis: YAxis) -> Point2D<isize> {
+    match axis {
+        YAxis::Down => Point2D::new(dx, dy),
+        YAxis::Up => Point2D::new(dx, -dy),
+    }
+}
+
+/// The four orthogonal directions, listed clockwise from up.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction4 {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    fn rotate(self, quarters: usize) -> Self {
+        Self::ALL[(self as usize + quarters) % 4]
+    }
+
+    /// Turns a quarter turn anticlockwise.
+    pub fn turn_left(self) -> Self {
+        self.rotate(3)
+    }
+
+    /// Turns a quarter turn clockwise.
+    pub fn turn_right(self) -> Self {
+        self.rotate(1)
+    }
+
+    pub fn reverse(self) -> Self {
+        self.rotate(2)
+    }
+}
+
+impl Heading for Direction4 {
+    fn offset(self, axis: YAxis) -> Point2D<isize> {
+        Direction8::from(self).offset(axis)
+    }
+}
+
+/// Accepts `U`/`D`/`L`/`R`, compass points `N`/`E`/`S`/`W`, and the arrows
+/// `^`/`v`/`<`/`>` and `↑`/`↓`/`←`/`→`.
+impl TryFrom<char> for Direction4 {
+    type Error = ParseDirectionError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'U' | 'N' | '^' | '↑' => Ok(Self::Up),
+            'R' | 'E' | '>' | '→' => Ok(Self::Right),
+            'D' | 'S' | 'v' | '↓' => Ok(Self::Down),
+            'L' | 'W' | '<' | '←' => Ok(Self::Left),
+            _ => Err(Self::Error::UnknownDirection),
+        }
+    }
+}
+
+impl FromStr for Direction4 {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::try_from(c),
+            _ => Err(Self::Err::UnknownDirection),
+        }
+    }
+}
+
+/// The four orthogonal and four diagonal directions, listed clockwise from
+/// up.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    pub const ALL: [Self; 8] = [
+        Self::Up, Self::UpRight, Self::Right, Self::DownRight,
+        Self::Down, Self::DownLeft, Self::Left, Self::UpLeft,
+    ];
+
+    fn rotate(self, eighths: usize) -> Self {
+        Self::ALL[(self as usize + eighths) % 8]
+    }
+
+    /// Turns a quarter turn anticlockwise, as for [`Direction4`].
+    pub fn turn_left(self) -> Self {
+        self.rotate(6)
+    }
+
+    /// Turns a quarter turn clockwise, as for [`Direction4`].
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    pub fn reverse(self) -> Self {
+        self.rotate(4)
+    }
+}
+
+impl Heading for Direction8 {
+    fn offset(self, axis: YAxis) -> Point2D<isize> {
+        let (dx, dy) = match self {
+            Self::Up => (0, -1),
+            Self::UpRight => (1, -1),
+            Self::Right => (1, 0),
+            Self::DownRight => (1, 1),
+            Self::Down => (0, 1),
+            Self::DownLeft => (-1, 1),
+            Self::Left => (-1, 0),
+            Self::UpLeft => (-1, -1),
+        };
+        oriented(dx, dy, axis)
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(dir: Direction4) -> Self {
+        Self::ALL[dir as usize * 2]
+    }
+}
+
+/// Accepts anything [`Direction4`] does, plus pairs such as `UR` or `NE`
+/// and the diagonal arrows `↗`/`↘`/`↙`/`↖`.
+impl FromStr for Direction8 {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "UR" | "NE" | "↗" => Ok(Self::UpRight),
+            "DR" | "SE" | "↘" => Ok(Self::DownRight),
+            "DL" | "SW" | "↙" => Ok(Self::DownLeft),
+            "UL" | "NW" | "↖" => Ok(Self::UpLeft),
+            _ => s.parse::<Direction4>().map(Self::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::point::Point2D;
+    use super::{Direction4, Direction8, Heading, YAxis};
+
+    #[test]
+    fn turns() {
+        assert_eq!(Direction4::Up.turn_left(), Direction4::Left);
+        assert_eq!(Direction4::Left.turn_right(), Direction4::Up);
+        assert_eq!(Direction4::Right.reverse(), Direction4::Left);
+        assert_eq!(Direction8::UpRight.turn_right(), Direction8::DownRight);
+        assert_eq!(Direction8::UpLeft.turn_left(), Direction8::DownLeft);
+        assert_eq!(Direction8::DownLeft.reverse(), Direction8::UpRight);
+    }
+
+    #[test]
+    fn offsets_follow_the_y_axis() {
+        assert_eq!(Direction4::Up.offset(YAxis::Down), Point2D::new(0, -1));
+        assert_eq!(Direction4::Up.offset(YAxis::Up), Point2D::new(0, 1));
+        assert_eq!(Direction8::DownLeft.offset(YAxis::Up), Point2D::new(-1, -1));
+        assert_eq!(Direction4::Left.grid_offset(), (0, -1));
+        assert_eq!(Point2D::new(2, 3).step(Direction8::UpRight, YAxis::Down, 2), Point2D::new(4, 1));
+    }
+
+    #[test]
+    fn parse() {
+        let dirs: Vec<Direction4> = ["U", "E", "v", "←"].iter().map(|s| s.parse().unwrap()).collect();
+        assert_eq!(dirs, vec![Direction4::Up, Direction4::Right, Direction4::Down, Direction4::Left]);
+        assert!("X".parse::<Direction4>().is_err());
+        assert!("UR".parse::<Direction4>().is_err());
+        assert_eq!("NW".parse::<Direction8>(), Ok(Direction8::UpLeft));
+        assert_eq!("S".parse::<Direction8>(), Ok(Direction8::Down));
+        assert_eq!(Direction4::try_from('>'), Ok(Direction4::Right));
+    }
+}
@@ -0,0 +1,250 @@
+use std::error;
+use std::fmt;
+
+use crate::grid::ParseGridError;
+use crate::input::{InputError, InputErrorKind, STR_SOURCE};
+use crate::{assignment, cave, cli, cpu, crane, days, direction, inttree, monkey, netpbm, output, point, rps, snake};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The input could not be read at all.
+    Io,
+    /// The input was read but is not in the expected shape.
+    Input,
+    /// A line or block of input could not be parsed into a puzzle type.
+    Parse,
+    /// The input parsed, but the puzzle has no answer for it.
+    NoSolution,
+    /// No solution is registered for the requested day and part.
+    Unregistered,
+}
+
+/// A location in the input. Lines and columns are 1-based, and a span with
+/// a column of zero covers the whole line.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    pub len: usize,
+}
+
+impl Span {
+    pub fn new(line: usize, column: usize, len: usize) -> Self {
+        Self { line, column, len }
+    }
+
+    pub fn line(line: usize) -> Self {
+        Self::new(line, 0, 0)
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.column == 0 {
+            write!(f, "{}", self.line)
+        } else {
+            write!(f, "{}:{}", self.line, self.column)
+        }
+    }
+}
+
+/// The error type shared by every module, so that callers such as the runner
+/// can report any failure the same way.
+#[derive(Debug)]
+pub struct Error {
+    kind: ErrorKind,
+    message: String,
+    source_name: Option<String>,
+    span: Option<Span>,
+    cause: Option<Box<dyn error::Error + Send + Sync + 'static>>,
+}
+
+impl Error {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            source_name: None,
+            span: None,
+            cause: None,
+        }
+    }
+
+    pub fn parse(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Parse, message)
+    }
+
+    pub fn no_solution(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::NoSolution, message)
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn source_name(&self) -> Option<&str> {
+        self.source_name.as_deref()
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+
+    /// Names the file or stream the error came from, unless it is already
+    /// known.
+    pub fn in_source(mut self, name: &str) -> Self {
+        if self.source_name.is_none() {
+            self.source_name = Some(name.to_owned());
+        }
+        self
+    }
+
+    /// Places the error in the input, unless it already has a location.
+    pub fn at(mut self, span: Span) -> Self {
+        if self.span.is_none() {
+            self.span = Some(span);
+        }
+        self
+    }
+
+    pub fn at_line(self, line: usize) -> Self {
+        self.at(Span::line(line))
+    }
+
+    /// Moves the error down by `lines`, for errors found while parsing a
+    /// slice that starts partway through the input.
+    pub fn shifted(mut self, lines: usize) -> Self {
+        if let Some(span) = self.span.as_mut() {
+            span.line += lines;
+        }
+        self
+    }
+
+    fn caused_by<E>(mut self, cause: E) -> Self
+    where
+        E: error::Error + Send + Sync + 'static,
+    {
+        self.cause = Some(Box::new(cause));
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.source_name, self.span) {
+            (Some(name), Some(span)) => write!(f, "{}:{}: ", name, span)?,
+            (Some(name), None) => write!(f, "{}: ", name)?,
+            (None, Some(span)) => write!(f, "line {}: ", span)?,
+            (None, None) => {}
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.cause.as_ref().map(|cause| cause.as_ref() as &(dyn error::Error + 'static))
+    }
+}
+
+impl From<InputError> for Error {
+    fn from(err: InputError) -> Self {
+        let kind = match err.kind {
+            InputErrorKind::Io(_) => ErrorKind::Io,
+            _ => ErrorKind::Input,
+        };
+        let mut error = Self::new(kind, err.kind.to_string());
+        // Input parsed from a string is named by whoever read the string
+        if err.source != STR_SOURCE {
+            error = error.in_source(&err.source);
+        }
+        if err.line > 0 {
+            error = error.at(Span::new(err.line, err.column, 1));
+        }
+        error.caused_by(err)
+    }
+}
+
+impl<E> From<ParseGridError<E>> for Error
+where
+    E: error::Error + Send + Sync + 'static,
+{
+    fn from(err: ParseGridError<E>) -> Self {
+        let span = Span::new(err.line, err.column, 1);
+        Self::parse(err.kind.to_string()).at(span).caused_by(err)
+    }
+}
+
+macro_rules! from_parse_error {
+    ($($err:ty),* $(,)?) => {
+        $(
+            impl From<$err> for Error {
+                fn from(err: $err) -> Self {
+                    Self::parse(err.to_string()).caused_by(err)
+                }
+            }
+        )*
+    };
+}
+
+from_parse_error!(
+    assignment::ParseError,
+    cave::ParseError,
+    cli::ParseError,
+    cpu::ParseInstructionError,
+    crane::ParseError,
+    days::ParsePartError,
+    direction::ParseDirectionError,
+    inttree::ParseError,
+    monkey::ParseError,
+    netpbm::ParseError,
+    output::ParseFormatError,
+    point::ParsePointError,
+    rps::ParseError,
+    snake::ParseMovementError,
+);
+
+#[cfg(test)]
+mod test {
+    use crate::grid::Grid;
+    use crate::input::{parse_lines, try_parse_uint_grid};
+    use crate::rps::Round;
+    use super::{Error, ErrorKind, Span};
+
+    #[test]
+    fn parse_errors_carry_their_line() {
+        let err = parse_lines::<Round>("A Y\nB\n").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Parse);
+        assert_eq!(err.span(), Some(Span::new(2, 1, 1)));
+        assert_eq!(err.to_string(), "line 2:1: expected two plays separated by whitespace");
+        assert_eq!(err.in_source("02.txt").to_string(), "02.txt:2:1: expected two plays separated by whitespace");
+    }
+
+    #[test]
+    fn input_errors_keep_their_column() {
+        let err: Error = try_parse_uint_grid("12\n1x\n").unwrap_err().into();
+        assert_eq!(err.kind(), ErrorKind::Input);
+        assert_eq!(err.source_name(), None);
+        assert_eq!(err.to_string(), "line 2:2: expected a digit, found 'x'");
+    }
+
+    #[test]
+    fn grid_errors_keep_their_column() {
+        let err: Error = "ab\nabc".parse::<Grid<char>>().unwrap_err().into();
+        assert_eq!(err.kind(), ErrorKind::Parse);
+        assert_eq!(err.to_string(), "line 2:3: expected a row of width 2, found 3");
+    }
+
+    #[test]
+    fn shifted_moves_the_span() {
+        let err = Error::parse("bad").at_line(2).shifted(5);
+        assert_eq!(err.span(), Some(Span::line(7)));
+        assert_eq!(err.to_string(), "line 7: bad");
+    }
+}
@@ -1,21 +1,258 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::error;
+use std::fmt;
 use std::ops::{Index, IndexMut};
+use std::str::FromStr;
 
-#[derive(Clone)]
+use crate::bitgrid::BitGrid;
+use crate::point::Point2D;
+
+/// Offsets as `(dy, dx)` to the cells sharing an edge with a cell, in
+/// reading order.
+pub const OFFSETS4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// Offsets as `(dy, dx)` to the cells sharing an edge or a corner with a
+/// cell, in reading order.
+pub const OFFSETS8: [(isize, isize); 8] = [
+    (-1, -1), (-1, 0), (-1, 1),
+    (0, -1), (0, 1),
+    (1, -1), (1, 0), (1, 1),
+];
+
+/// A cell position. Grids are stored by row, so tuples are `(y, x)`, while
+/// points name their coordinates and can be used without swapping them.
+pub trait Position: Copy + 'static {
+    fn row_col(self) -> (usize, usize);
+
+    fn from_row_col(row_col: (usize, usize)) -> Self;
+}
+
+impl Position for (usize, usize) {
+    fn row_col(self) -> (usize, usize) {
+        self
+    }
+
+    fn from_row_col(row_col: (usize, usize)) -> Self {
+        row_col
+    }
+}
+
+impl Position for Point2D<usize> {
+    fn row_col(self) -> (usize, usize) {
+        (self.y, self.x)
+    }
+
+    fn from_row_col((y, x): (usize, usize)) -> Self {
+        Point2D::new(x, y)
+    }
+}
+
+/// Which cells count as touching, for region searches.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Cells touch when they share an edge.
+    Four,
+    /// Cells touch when they share an edge or a corner.
+    Eight,
+}
+
+impl Connectivity {
+    pub fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Self::Four => &OFFSETS4,
+            Self::Eight => &OFFSETS8,
+        }
+    }
+}
+
+/// The connected regions of a grid, as found by [`Grid::components`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Components {
+    /// The region each cell belongs to, numbered from 0 in the order their
+    /// first cells appear in reading order. Cells in no region are `None`.
+    pub labels: Grid<Option<usize>>,
+    /// The number of cells in each region, indexed by label.
+    pub sizes: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGridErrorKind<E> {
+    /// The cell conversion rejected a character.
+    Cell(E),
+    InconsistentWidth { expected: usize, found: usize },
+}
+
+/// An error parsing a grid from text. The line and column are 1-based, and
+/// the column is the byte offset of the offending character on its line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseGridError<E> {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseGridErrorKind<E>,
+}
+
+impl<E: fmt::Display> fmt::Display for ParseGridErrorKind<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cell(err) => write!(f, "{}", err),
+            Self::InconsistentWidth { expected, found } => {
+                write!(f, "expected a row of width {}, found {}", expected, found)
+            }
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for ParseGridError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.kind)
+    }
+}
+
+impl<E: error::Error + 'static> error::Error for ParseGridError<E> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match &self.kind {
+            ParseGridErrorKind::Cell(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Grid<T> {
     cells: Vec<T>,
     rows: usize,
     cols: usize,
+    origin: Point2D<isize>,
 }
 
 impl<T> Grid<T> {
     pub fn new_with_cells(cells: Vec<T>, rows: usize, cols: usize) -> Self {
-        Grid { cells, rows, cols }
+        Grid { cells, rows, cols, origin: Point2D::new(0, 0) }
+    }
+
+    /// Parses one cell per character, one row per line, converting each
+    /// character along with its `(y, x)` position. Every row must be as wide
+    /// as the first. Input with only empty lines gives an empty grid.
+    pub fn parse_with<E, F>(s: &str, mut cell: F) -> Result<Self, ParseGridError<E>>
+    where
+        F: FnMut(char, (usize, usize)) -> Result<T, E>,
+    {
+        let mut rows = 0;
+        let mut cols = 0;
+        let mut cells: Vec<T> = Vec::new();
+        for (y, line) in s.lines().enumerate() {
+            rows += 1;
+            let mut width = 0;
+            for (col, c) in line.char_indices() {
+                let error = |kind| ParseGridError { line: y + 1, column: col + 1, kind };
+                if rows > 1 && width == cols {
+                    let found = line.chars().count();
+                    return Err(error(ParseGridErrorKind::InconsistentWidth { expected: cols, found }));
+                }
+                cells.push(cell(c, (y, width)).map_err(|err| error(ParseGridErrorKind::Cell(err)))?);
+                width += 1;
+            }
+            if rows == 1 {
+                cols = width;
+            } else if width != cols {
+                let kind = ParseGridErrorKind::InconsistentWidth { expected: cols, found: width };
+                return Err(ParseGridError { line: y + 1, column: line.len() + 1, kind });
+            }
+        }
+        if cols == 0 {
+            rows = 0;
+        }
+        Ok(Self::new_with_cells(cells, rows, cols))
+    }
+
+    /// Builds a grid by calling `f` with each position in reading order.
+    pub fn from_fn<F>(rows: usize, cols: usize, mut f: F) -> Self
+    where
+        F: FnMut((usize, usize)) -> T,
+    {
+        let mut cells = Vec::with_capacity(rows * cols);
+        for y in 0..rows {
+            for x in 0..cols {
+                cells.push(f((y, x)));
+            }
+        }
+        Self::new_with_cells(cells, rows, cols)
     }
 
     pub fn size(&self) -> (usize, usize) {
         (self.rows, self.cols)
     }
 
+    /// Iterates over the rows from top to bottom.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        (0..self.rows).map(move |y| &self[y])
+    }
+
+    /// Iterates down column `x`.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(x < self.cols, "column {} is out of bounds for {} columns", x, self.cols);
+        (0..self.rows).map(move |y| &self[(y, x)])
+    }
+
+    /// Iterates over the columns from left to right, each from top to
+    /// bottom.
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.cols).map(move |x| self.column(x))
+    }
+
+    /// Borrows the whole grid as a [`View`].
+    pub fn view(&self) -> View<'_, T> {
+        View {
+            grid: self,
+            top: 0,
+            left: 0,
+            rows: self.rows,
+            cols: self.cols,
+            transposed: false,
+        }
+    }
+
+    /// Borrows the `size` cells with their top left corner at `pos`.
+    /// Panics if they do not fit in the grid, as slicing does.
+    pub fn subgrid(&self, pos: (usize, usize), size: (usize, usize)) -> View<'_, T> {
+        self.view().subgrid(pos, size)
+    }
+
+    /// Iterates over every `h` by `w` view that fits in the grid, in reading
+    /// order of their top left corners. Yields nothing if the grid is
+    /// smaller than a window. Panics if either side is zero, as
+    /// `slice::windows` does.
+    pub fn windows(&self, h: usize, w: usize) -> impl Iterator<Item = View<'_, T>> {
+        assert!(h > 0 && w > 0, "window size must be non-zero");
+        let ys = 0..(self.rows + 1).saturating_sub(h);
+        let xs = 0..(self.cols + 1).saturating_sub(w);
+        ys.flat_map(move |y| xs.clone().map(move |x| self.subgrid((y, x), (h, w))))
+    }
+
+    /// Builds a grid of the same size and origin by laying `kernel` over each
+    /// cell, with the kernel's centre at `(rows / 2, cols / 2)`. `combine` is
+    /// given the cell and the kernel values paired with the cells under them.
+    /// Pairs that fall outside the grid are left out, so edge cells see
+    /// fewer.
+    pub fn convolve<K, U, F>(&self, kernel: &Grid<K>, mut combine: F) -> Grid<U>
+    where
+        F: FnMut(&T, &[(&K, &T)]) -> U,
+    {
+        let centre = (kernel.rows / 2, kernel.cols / 2);
+        let mut pairs = Vec::with_capacity(kernel.cells.len());
+        self.map(|(y, x), cell| {
+            pairs.clear();
+            for (ky, kx, weight) in kernel.iter() {
+                let offset = (ky as isize - centre.0 as isize, kx as isize - centre.1 as isize);
+                if let Some(pos) = self.offset((y, x), offset) {
+                    pairs.push((weight, &self[pos]));
+                }
+            }
+            combine(cell, &pairs)
+        })
+    }
+
     pub fn enumerate<F>(&self, mut f: F)
     where
         F: FnMut((usize, usize), &T),
@@ -28,9 +265,11 @@ impl<T> Grid<T> {
         }
     }
 
-    pub fn map<F>(&self, mut f: F) -> Self
+    /// Builds a grid of the same size and origin from each cell and its
+    /// position.
+    pub fn map<U, F>(&self, mut f: F) -> Grid<U>
     where
-        F: FnMut((usize, usize), &T) -> T,
+        F: FnMut((usize, usize), &T) -> U,
     {
         let mut cells = Vec::with_capacity(self.cells.len());
         for y in 0..self.rows {
@@ -39,16 +278,437 @@ impl<T> Grid<T> {
                 cells.push(f((y, x), cell));
             }
         }
-        Self::new_with_cells(cells, self.rows, self.cols)
+        let mut grid = Grid::new_with_cells(cells, self.rows, self.cols);
+        grid.origin = self.origin;
+        grid
+    }
+
+    /// Returns the position `offset` away from `pos`, if it is in the grid.
+    pub fn offset<P: Position>(&self, pos: P, offset: (isize, isize)) -> Option<P> {
+        let (y, x) = pos.row_col();
+        let y = y.checked_add_signed(offset.0).filter(|&y| y < self.rows)?;
+        let x = x.checked_add_signed(offset.1).filter(|&x| x < self.cols)?;
+        Some(P::from_row_col((y, x)))
+    }
+
+    /// Returns the cell at `pos`, or None if it is outside the grid.
+    pub fn get<P: Position>(&self, pos: P) -> Option<&T> {
+        let (y, x) = pos.row_col();
+        (y < self.rows && x < self.cols).then(|| &self.cells[y * self.cols + x])
+    }
+
+    pub fn get_mut<P: Position>(&mut self, pos: P) -> Option<&mut T> {
+        let (y, x) = pos.row_col();
+        (y < self.rows && x < self.cols).then(|| &mut self.cells[y * self.cols + x])
+    }
+
+    /// Iterates over the in-bounds cells at each of `offsets` from `pos`,
+    /// with their positions, in the order the offsets are given.
+    pub fn neighbours<'a, P: Position>(
+        &'a self,
+        pos: P,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (P, &'a T)> + 'a {
+        offsets.iter()
+            .filter_map(move |&offset| self.offset(pos, offset))
+            .map(move |pos| (pos, &self[pos.row_col()]))
+    }
+
+    /// Iterates over the cells sharing an edge with `pos`.
+    pub fn neighbours4<P: Position>(&self, pos: P) -> impl Iterator<Item = (P, &T)> {
+        self.neighbours(pos, &OFFSETS4)
+    }
+
+    /// Iterates over the cells sharing an edge or a corner with `pos`.
+    pub fn neighbours8<P: Position>(&self, pos: P) -> impl Iterator<Item = (P, &T)> {
+        self.neighbours(pos, &OFFSETS8)
+    }
+
+    /// Finds the cells reachable from `start` by moving between touching
+    /// cells that satisfy `predicate`. The region is empty if `start` itself
+    /// does not satisfy it or is outside the grid.
+    pub fn flood_fill<P, F>(&self, start: P, connectivity: Connectivity, mut predicate: F) -> BitGrid
+    where
+        P: Position,
+        F: FnMut(&T) -> bool,
+    {
+        let mut region = BitGrid::new(self.rows, self.cols);
+        self.fill(start.row_col(), connectivity, &mut predicate, |pos| !region.set(pos, true));
+        region
+    }
+
+    /// Visits a region depth first, calling `claim` on each candidate cell.
+    /// `claim` returns false for cells that were already visited.
+    fn fill<P, C>(&self, start: (usize, usize), connectivity: Connectivity, predicate: &mut P, mut claim: C)
+    where
+        P: FnMut(&T) -> bool,
+        C: FnMut((usize, usize)) -> bool,
+    {
+        if !self.get(start).is_some_and(&mut *predicate) || !claim(start) {
+            return;
+        }
+        let mut stack = vec![start];
+        while let Some(pos) = stack.pop() {
+            for (next, cell) in self.neighbours(pos, connectivity.offsets()) {
+                if predicate(cell) && claim(next) {
+                    stack.push(next);
+                }
+            }
+        }
+    }
+
+    /// Labels every region of touching cells that satisfy `predicate`. The
+    /// labels share the grid's origin.
+    pub fn components<F>(&self, connectivity: Connectivity, mut predicate: F) -> Components
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut labels: Grid<Option<usize>> = Grid::new(self.rows, self.cols);
+        labels.origin = self.origin;
+        let mut sizes: Vec<usize> = Vec::new();
+        for y in 0..self.rows {
+            for x in 0..self.cols {
+                if labels[(y, x)].is_some() {
+                    continue;
+                }
+                let label = sizes.len();
+                let mut size = 0;
+                self.fill((y, x), connectivity, &mut predicate, |pos| {
+                    if labels[pos].is_some() {
+                        return false;
+                    }
+                    labels[pos] = Some(label);
+                    size += 1;
+                    true
+                });
+                if size > 0 {
+                    sizes.push(size);
+                }
+            }
+        }
+        Components { labels, sizes }
     }
 
-    pub fn iter(&self) -> Iter<T> {
+    /// Iterates over the cells in a straight line from `pos`, stepping by
+    /// `direction` as `(dy, dx)` until leaving the grid. The cell at `pos`
+    /// itself is not included.
+    pub fn ray<P: Position>(&self, pos: P, direction: (isize, isize)) -> impl Iterator<Item = (P, &T)> {
+        std::iter::successors(self.offset(pos, direction), move |&pos| self.offset(pos, direction))
+            .map(move |pos| (pos, &self[pos.row_col()]))
+    }
+
+    /// Draws the grid as text, one line per row, with `glyph` choosing the
+    /// character for each cell. The result implements `Display`, and can add
+    /// index gutters or highlight cells first.
+    pub fn render<F>(&self, glyph: F) -> Render<'_, T, F>
+    where
+        F: Fn(&T) -> char,
+    {
+        Render {
+            grid: self,
+            glyph,
+            gutters: false,
+            highlights: HashMap::new(),
+        }
+    }
+
+    /// Iterates over the cells in reading order with their positions as
+    /// points.
+    pub fn iter_points(&self) -> impl Iterator<Item = (Point2D<usize>, &T)> {
+        self.iter().map(|(y, x, cell)| (Point2D::new(x, y), cell))
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
         Iter {
             grid: self,
             x: 0,
             y: 0,
         }
     }
+
+    /// Returns the world coordinates of cell `(0, 0)`. The origin starts at
+    /// zero and moves as the grid grows or is cropped, so a point keeps
+    /// naming the same cell.
+    pub fn origin(&self) -> Point2D<isize> {
+        self.origin
+    }
+
+    pub fn set_origin(&mut self, origin: Point2D<isize>) {
+        self.origin = origin;
+    }
+
+    /// Returns the cell position of a world point, or None if it is outside
+    /// the grid.
+    pub fn to_local(&self, p: Point2D<isize>) -> Option<(usize, usize)> {
+        let y = usize::try_from(p.y - self.origin.y).ok().filter(|&y| y < self.rows)?;
+        let x = usize::try_from(p.x - self.origin.x).ok().filter(|&x| x < self.cols)?;
+        Some((y, x))
+    }
+
+    /// Returns the world point of a cell position.
+    pub fn to_world<P: Position>(&self, pos: P) -> Point2D<isize> {
+        let (y, x) = pos.row_col();
+        Point2D::new(self.origin.x + x as isize, self.origin.y + y as isize)
+    }
+
+    /// Shrinks the grid to the smallest rectangle holding every cell that
+    /// satisfies `predicate`, moving the origin with its top left corner.
+    /// The grid becomes empty if no cell does.
+    pub fn crop_to<F>(&mut self, mut predicate: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut bounds: Option<((usize, usize), (usize, usize))> = None;
+        for (y, x, cell) in self.iter() {
+            if predicate(cell) {
+                let ((top, left), (bottom, right)) = bounds.get_or_insert(((y, x), (y, x)));
+                *top = (*top).min(y);
+                *left = (*left).min(x);
+                *bottom = (*bottom).max(y);
+                *right = (*right).max(x);
+            }
+        }
+        let ((top, left), (bottom, right)) = match bounds {
+            Some(bounds) => bounds,
+            None => {
+                self.cells.clear();
+                (self.rows, self.cols) = (0, 0);
+                return;
+            }
+        };
+        let cols = self.cols;
+        let cells = std::mem::take(&mut self.cells);
+        self.cells = cells.into_iter()
+            .enumerate()
+            .filter(|(i, _)| (top..=bottom).contains(&(i / cols)) && (left..=right).contains(&(i % cols)))
+            .map(|(_, cell)| cell)
+            .collect();
+        self.rows = bottom - top + 1;
+        self.cols = right - left + 1;
+        self.origin.y += top as isize;
+        self.origin.x += left as isize;
+    }
+}
+
+/// The transforms keep the origin, so the new top left cell sits at the
+/// world point of the old one.
+impl<T: Clone> Grid<T> {
+    fn transformed<F>(&self, rows: usize, cols: usize, f: F) -> Self
+    where
+        F: FnMut((usize, usize)) -> T,
+    {
+        let mut grid = Self::from_fn(rows, cols, f);
+        grid.origin = self.origin;
+        grid
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Self {
+        self.transformed(self.cols, self.rows, |(y, x)| self[(x, y)].clone())
+    }
+
+    /// Turns the grid a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Self {
+        self.transformed(self.cols, self.rows, |(y, x)| self[(self.rows - 1 - x, y)].clone())
+    }
+
+    /// Turns the grid a quarter turn anticlockwise.
+    pub fn rotate_ccw(&self) -> Self {
+        self.transformed(self.cols, self.rows, |(y, x)| self[(x, self.cols - 1 - y)].clone())
+    }
+
+    /// Mirrors the grid left to right.
+    pub fn flip_h(&self) -> Self {
+        self.transformed(self.rows, self.cols, |(y, x)| self[(y, self.cols - 1 - x)].clone())
+    }
+
+    /// Mirrors the grid top to bottom.
+    pub fn flip_v(&self) -> Self {
+        self.transformed(self.rows, self.cols, |(y, x)| self[(self.rows - 1 - y, x)].clone())
+    }
+
+    /// Adds the given number of `fill` cells on each side, keeping the
+    /// existing cells at the same world points.
+    fn expand(&mut self, (top, bottom): (usize, usize), (left, right): (usize, usize), fill: T) {
+        let rows = self.rows + top + bottom;
+        let cols = self.cols + left + right;
+        let mut cells = Vec::with_capacity(rows * cols);
+        cells.resize(top * cols, fill.clone());
+        let mut old = std::mem::take(&mut self.cells).into_iter();
+        for _ in 0..self.rows {
+            cells.extend(std::iter::repeat_n(fill.clone(), left));
+            cells.extend(old.by_ref().take(self.cols));
+            cells.extend(std::iter::repeat_n(fill.clone(), right));
+        }
+        cells.resize(rows * cols, fill);
+        self.cells = cells;
+        (self.rows, self.cols) = (rows, cols);
+        self.origin.y -= top as isize;
+        self.origin.x -= left as isize;
+    }
+
+    /// Surrounds the grid with a border `n` cells wide of `fill`. The origin
+    /// moves so that existing cells keep their world points.
+    pub fn pad(&mut self, n: usize, fill: T) {
+        self.expand((n, n), (n, n), fill);
+    }
+
+    /// Grows the grid with `fill` cells just enough to hold the world point
+    /// `p`, and returns its cell position. An empty grid becomes a single
+    /// cell at `p`.
+    pub fn grow_to_include(&mut self, p: Point2D<isize>, fill: T) -> (usize, usize) {
+        if self.cells.is_empty() {
+            *self = Self::new_with_cells(vec![fill], 1, 1);
+            self.origin = p;
+            return (0, 0);
+        }
+        let before = |at: isize, origin: isize| origin.saturating_sub(at).max(0) as usize;
+        let after = |at: isize, origin: isize, len: usize| {
+            at.saturating_sub(origin + len as isize - 1).max(0) as usize
+        };
+        let rows = (before(p.y, self.origin.y), after(p.y, self.origin.y, self.rows));
+        let cols = (before(p.x, self.origin.x), after(p.x, self.origin.x, self.cols));
+        if rows != (0, 0) || cols != (0, 0) {
+            self.expand(rows, cols, fill);
+        }
+        self.to_local(p).expect("grown to hold the point")
+    }
+
+    /// Inserts a row of `fill` before row `y`, moving the rows below it down.
+    /// Panics if `y` is past the last row. The origin does not change.
+    pub fn insert_row(&mut self, y: usize, fill: T) {
+        assert!(y <= self.rows, "row {} is out of bounds for {} rows", y, self.rows);
+        let at = y * self.cols;
+        self.cells.splice(at..at, std::iter::repeat_n(fill, self.cols));
+        self.rows += 1;
+    }
+
+    /// Inserts a column of `fill` before column `x`, moving the columns to
+    /// its right along. Panics if `x` is past the last column. The origin
+    /// does not change.
+    pub fn insert_col(&mut self, x: usize, fill: T) {
+        assert!(x <= self.cols, "column {} is out of bounds for {} columns", x, self.cols);
+        let cols = self.cols + 1;
+        let mut cells = Vec::with_capacity(self.rows * cols);
+        let mut old = std::mem::take(&mut self.cells).into_iter();
+        for _ in 0..self.rows {
+            cells.extend(old.by_ref().take(x));
+            cells.push(fill.clone());
+            cells.extend(old.by_ref().take(self.cols - x));
+        }
+        self.cells = cells;
+        self.cols = cols;
+    }
+}
+
+/// A borrowed rectangle of a grid, possibly transposed. Positions are
+/// relative to the view, so `(0, 0)` is always its top left cell.
+pub struct View<'a, T> {
+    grid: &'a Grid<T>,
+    top: usize,
+    left: usize,
+    rows: usize,
+    cols: usize,
+    transposed: bool,
+}
+
+// Derived impls would require T: Clone
+impl<T> Clone for View<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for View<'_, T> {}
+
+impl<'a, T> View<'a, T> {
+    pub fn size(&self) -> (usize, usize) {
+        if self.transposed {
+            (self.cols, self.rows)
+        } else {
+            (self.rows, self.cols)
+        }
+    }
+
+    /// Maps a position in the view to one in the underlying grid.
+    fn grid_pos(&self, (y, x): (usize, usize)) -> Option<(usize, usize)> {
+        let (y, x) = if self.transposed { (x, y) } else { (y, x) };
+        (y < self.rows && x < self.cols).then_some((self.top + y, self.left + x))
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&'a T> {
+        self.grid_pos(pos).map(|pos| &self.grid[pos])
+    }
+
+    /// Swaps the rows and columns of the view without copying.
+    pub fn transpose(self) -> Self {
+        Self { transposed: !self.transposed, ..self }
+    }
+
+    /// Narrows the view to the `size` cells with their top left corner at
+    /// `pos`. Panics if they do not fit in the view.
+    pub fn subgrid(self, pos: (usize, usize), size: (usize, usize)) -> Self {
+        let (rows, cols) = self.size();
+        assert!(
+            pos.0 + size.0 <= rows && pos.1 + size.1 <= cols,
+            "subgrid of {:?} at {:?} does not fit in {:?}", size, pos, (rows, cols),
+        );
+        let ((y, x), (h, w)) = if self.transposed {
+            ((pos.1, pos.0), (size.1, size.0))
+        } else {
+            (pos, size)
+        };
+        Self { top: self.top + y, left: self.left + x, rows: h, cols: w, ..self }
+    }
+
+    /// Iterates along row `y` of the view.
+    pub fn row(self, y: usize) -> impl DoubleEndedIterator<Item = &'a T> {
+        let (rows, cols) = self.size();
+        assert!(y < rows, "row {} is out of bounds for {} rows", y, rows);
+        (0..cols).map(move |x| self.get((y, x)).expect("position is in the view"))
+    }
+
+    /// Iterates over the rows of the view from top to bottom.
+    pub fn rows(self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &'a T>> {
+        (0..self.size().0).map(move |y| self.row(y))
+    }
+
+    /// Iterates over the cells in reading order with their positions in the
+    /// view.
+    pub fn iter(self) -> impl Iterator<Item = (usize, usize, &'a T)> {
+        (0..self.size().0).flat_map(move |y| self.row(y).enumerate().map(move |(x, cell)| (y, x, cell)))
+    }
+}
+
+impl<T: Clone> View<'_, T> {
+    /// Copies the view into a grid of its own, with its origin at zero.
+    pub fn to_grid(&self) -> Grid<T> {
+        let (rows, cols) = self.size();
+        Grid::from_fn(rows, cols, |pos| self[pos].clone())
+    }
+}
+
+impl<T> Index<(usize, usize)> for View<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is out of bounds for a view of {:?}", pos, self.size()))
+    }
+}
+
+impl<T> Index<Point2D<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point2D<usize>) -> &Self::Output {
+        &self[(p.y, p.x)]
+    }
+}
+
+impl<T> IndexMut<Point2D<usize>> for Grid<T> {
+    fn index_mut(&mut self, p: Point2D<usize>) -> &mut Self::Output {
+        &mut self[(p.y, p.x)]
+    }
 }
 
 impl<T> Index<usize> for Grid<T> {
@@ -87,7 +747,106 @@ impl<T: Default> Grid<T> {
     pub fn new(rows: usize, cols: usize) -> Grid<T> {
         let mut cells = Vec::new();
         cells.resize_with(rows * cols, T::default);
-        Grid { cells, rows, cols }
+        Self::new_with_cells(cells, rows, cols)
+    }
+}
+
+/// A grid being drawn as text, as returned by [`Grid::render`].
+pub struct Render<'a, T, F> {
+    grid: &'a Grid<T>,
+    glyph: F,
+    gutters: bool,
+    highlights: HashMap<(usize, usize), char>,
+}
+
+impl<T, F> Render<'_, T, F> {
+    /// Labels each row on the left and each column along the top with its
+    /// index. Column indices are written downwards, one digit per line.
+    pub fn gutters(mut self) -> Self {
+        self.gutters = true;
+        self
+    }
+
+    /// Draws `glyph` at each of `positions` instead of the cell there.
+    /// Positions outside the grid are ignored.
+    pub fn highlight<I, P>(mut self, positions: I, glyph: char) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Position,
+    {
+        self.highlights.extend(positions.into_iter().map(|pos| (pos.row_col(), glyph)));
+        self
+    }
+}
+
+fn digits(n: usize) -> usize {
+    n.checked_ilog10().unwrap_or(0) as usize + 1
+}
+
+impl<T, F> fmt::Display for Render<'_, T, F>
+where
+    F: Fn(&T) -> char,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (rows, cols) = self.grid.size();
+        let row_width = digits(rows.saturating_sub(1));
+        let mut first = true;
+        if self.gutters && cols > 0 {
+            for place in (0..digits(cols - 1)).rev() {
+                let scale = 10_usize.pow(place as u32);
+                // Leading zeros are left blank
+                let line: String = (0..cols)
+                    .map(|x| {
+                        if x >= scale || place == 0 {
+                            char::from(b'0' + (x / scale % 10) as u8)
+                        } else {
+                            ' '
+                        }
+                    })
+                    .collect();
+                if !first {
+                    writeln!(f)?;
+                }
+                write!(f, "{:row_width$} {}", "", line)?;
+                first = false;
+            }
+        }
+        for y in 0..rows {
+            if !first {
+                writeln!(f)?;
+            }
+            if self.gutters {
+                write!(f, "{:>row_width$} ", y)?;
+            }
+            let line: String = self.grid[y].iter()
+                .enumerate()
+                .map(|(x, cell)| self.highlights.get(&(y, x)).copied().unwrap_or_else(|| (self.glyph)(cell)))
+                .collect();
+            write!(f, "{}", line)?;
+            first = false;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = ParseGridError<Infallible>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, |c, _| Ok(c))
+    }
+}
+
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(|&c| c))
+    }
+}
+
+/// Set cells are drawn as `#` and clear cells as `.`.
+impl fmt::Display for Grid<bool> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(|&set| if set { '#' } else { '.' }))
     }
 }
 
@@ -103,7 +862,9 @@ impl<'a, T> Iterator for Iter<'a, T> {
     fn next(&mut self) -> Option<Self::Item> {
         let (rows, cols) = self.grid.size();
         let (x, y) = (self.x, self.y);
-        if y == rows {
+        // A grid can have rows of no cells, for example after inserting a
+        // row into an empty one
+        if y == rows || cols == 0 {
             return None;
         }
         let item = &self.grid[y][x];
@@ -116,3 +877,304 @@ impl<'a, T> Iterator for Iter<'a, T> {
         Some((y, x, item))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::point::Point2D;
+    use super::{Connectivity, Grid, ParseGridErrorKind};
+
+    fn numbered(rows: usize, cols: usize) -> Grid<usize> {
+        Grid::new_with_cells((0..rows * cols).collect(), rows, cols)
+    }
+
+    #[test]
+    fn neighbours4_stay_in_bounds() {
+        let grid = numbered(3, 4);
+        let corner: Vec<_> = grid.neighbours4((0, 0)).collect();
+        assert_eq!(corner, vec![((0, 1), &1), ((1, 0), &4)]);
+        let middle: Vec<_> = grid.neighbours4((1, 1)).map(|(_, &n)| n).collect();
+        assert_eq!(middle, vec![1, 4, 6, 9]);
+        let edge: Vec<_> = grid.neighbours4((2, 3)).map(|(pos, _)| pos).collect();
+        assert_eq!(edge, vec![(1, 3), (2, 2)]);
+    }
+
+    #[test]
+    fn neighbours8_include_corners() {
+        let grid = numbered(3, 4);
+        let middle: Vec<_> = grid.neighbours8((1, 1)).map(|(_, &n)| n).collect();
+        assert_eq!(middle, vec![0, 1, 2, 4, 6, 8, 9, 10]);
+        let corner: Vec<_> = grid.neighbours8((2, 3)).map(|(_, &n)| n).collect();
+        assert_eq!(corner, vec![6, 7, 10]);
+    }
+
+    #[test]
+    fn custom_offsets() {
+        let grid = numbered(3, 4);
+        let knight = [(-2, -1), (-2, 1), (-1, -2), (-1, 2), (1, -2), (1, 2), (2, -1), (2, 1)];
+        let moves: Vec<_> = grid.neighbours((0, 0), &knight).map(|(pos, _)| pos).collect();
+        assert_eq!(moves, vec![(1, 2), (2, 1)]);
+        assert_eq!(grid.offset((0, 3), (0, 1)), None);
+        assert_eq!(grid.offset((2, 3), (-2, -3)), Some((0, 0)));
+    }
+
+    #[test]
+    fn display_chars_and_bools() {
+        let chars = Grid::new_with_cells("abcd".chars().collect(), 2, 2);
+        assert_eq!(chars.to_string(), "ab\ncd");
+        let bools = Grid::new_with_cells(vec![true, false, false, true], 2, 2);
+        assert_eq!(bools.to_string(), "#.\n.#");
+        assert_eq!(Grid::<bool>::new(0, 0).to_string(), "");
+    }
+
+    #[test]
+    fn render_with_gutters_and_highlights() {
+        let grid = numbered(11, 12);
+        let text = grid.render(|&n| if n % 2 == 0 { '.' } else { '#' })
+            .gutters()
+            .highlight([(0, 0), (10, 11), (20, 20)], '@')
+            .to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 13);
+        assert_eq!(lines[0], "             11");
+        assert_eq!(lines[1], "   012345678901");
+        assert_eq!(lines[2], " 0 @#.#.#.#.#.#");
+        assert_eq!(lines[12], "10 .#.#.#.#.#.@");
+        let glyph = |&n: &usize| char::from(b'0' + (n % 10) as u8);
+        let by_point = grid.render(glyph).highlight([Point2D::new(11, 10)], '@').to_string();
+        assert_eq!(by_point, grid.render(glyph).highlight([(10, 11)], '@').to_string());
+        assert!(by_point.lines().nth(10).unwrap().ends_with('@'));
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Tile {
+        Open,
+        Wall,
+    }
+
+    #[test]
+    fn parse_with_converts_each_cell() {
+        let grid = Grid::parse_with(".#\n#.\n", |c, pos| match c {
+            '.' => Ok(Tile::Open),
+            '#' => Ok(Tile::Wall),
+            _ => Err(pos),
+        });
+        assert_eq!(grid.unwrap().column(0).collect::<Vec<_>>(), vec![&Tile::Open, &Tile::Wall]);
+        let err = Grid::parse_with("..\n.x", |c, pos| if c == '.' { Ok(Tile::Open) } else { Err(pos) })
+            .unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.kind, ParseGridErrorKind::Cell((1, 1)));
+    }
+
+    #[test]
+    fn parse_chars_rejects_ragged_rows() {
+        let grid: Grid<char> = "ab\ncd".parse().unwrap();
+        assert_eq!(grid.to_string(), "ab\ncd");
+        let err = "abc\nab".parse::<Grid<char>>().unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.kind, ParseGridErrorKind::InconsistentWidth { expected: 3, found: 2 });
+        assert_eq!(err.to_string(), "line 2, column 3: expected a row of width 3, found 2");
+    }
+
+    #[test]
+    fn blank_input_parses_as_an_empty_grid() {
+        for input in ["", "\n", "\n\n", "\r\n"] {
+            let grid: Grid<char> = input.parse().unwrap();
+            assert_eq!(grid.size(), (0, 0), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn transforms() {
+        // 0 1 2
+        // 3 4 5
+        let grid = numbered(2, 3);
+        let cells = |grid: Grid<usize>| (grid.size(), grid.iter().map(|(_, _, &n)| n).collect::<Vec<_>>());
+        assert_eq!(cells(grid.transpose()), ((3, 2), vec![0, 3, 1, 4, 2, 5]));
+        assert_eq!(cells(grid.rotate_cw()), ((3, 2), vec![3, 0, 4, 1, 5, 2]));
+        assert_eq!(cells(grid.rotate_ccw()), ((3, 2), vec![2, 5, 1, 4, 0, 3]));
+        assert_eq!(cells(grid.flip_h()), ((2, 3), vec![2, 1, 0, 5, 4, 3]));
+        assert_eq!(cells(grid.flip_v()), ((2, 3), vec![3, 4, 5, 0, 1, 2]));
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(grid.rotate_cw().rotate_cw(), grid.flip_h().flip_v());
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = numbered(2, 3);
+        let columns: Vec<Vec<usize>> = grid.columns().map(|col| col.copied().collect()).collect();
+        assert_eq!(columns, vec![vec![0, 3], vec![1, 4], vec![2, 5]]);
+        assert_eq!(grid.column(2).rev().copied().collect::<Vec<_>>(), vec![5, 2]);
+        assert_eq!(grid.rows().last(), Some(&[3, 4, 5][..]));
+    }
+
+    #[test]
+    fn views() {
+        let grid = numbered(4, 5);
+        let view = grid.subgrid((1, 2), (2, 3));
+        assert_eq!(view.size(), (2, 3));
+        assert_eq!((view[(0, 0)], view[(1, 2)]), (7, 14));
+        assert_eq!(view.get((2, 0)), None);
+        let transposed = view.transpose();
+        assert_eq!(transposed.size(), (3, 2));
+        assert_eq!(transposed.row(2).copied().collect::<Vec<_>>(), vec![9, 14]);
+        assert_eq!(transposed.subgrid((1, 1), (2, 1)).to_grid(), Grid::new_with_cells(vec![13, 14], 2, 1));
+        assert_eq!(grid.view().transpose().to_grid(), grid.transpose());
+        let cells: Vec<usize> = view.iter().map(|(_, _, &n)| n).collect();
+        assert_eq!(cells, vec![7, 8, 9, 12, 13, 14]);
+        assert_eq!(view.rows().count(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn subgrid_out_of_bounds() {
+        numbered(2, 2).subgrid((1, 1), (2, 1));
+    }
+
+    #[test]
+    fn flood_fill_stays_in_its_region() {
+        let grid: Grid<char> = "..#.\n.#..\n#...".parse().unwrap();
+        let open = |&c: &char| c == '.';
+        let four = grid.flood_fill((0, 0), Connectivity::Four, open);
+        assert_eq!(four.to_string(), "##..\n#...\n....");
+        let eight = grid.flood_fill((0, 0), Connectivity::Eight, open);
+        assert_eq!(eight.count_ones(), 9);
+        assert_eq!(grid.flood_fill((0, 2), Connectivity::Four, open).count_ones(), 0);
+        // (0, 4) would alias the open cell (1, 0) if it were not bounds checked
+        assert_eq!(grid.flood_fill((0, 4), Connectivity::Four, open).count_ones(), 0);
+        assert_eq!(grid.flood_fill((3, 0), Connectivity::Four, open).size(), (3, 4));
+        assert_eq!(grid.flood_fill(Point2D::new(0, 1), Connectivity::Four, open), four);
+    }
+
+    #[test]
+    fn components_label_each_region() {
+        let grid: Grid<char> = "..#.\n.#..\n#...".parse().unwrap();
+        let four = grid.components(Connectivity::Four, |&c| c == '.');
+        assert_eq!(four.sizes, vec![3, 6]);
+        assert_eq!(four.labels.column(3).copied().collect::<Vec<_>>(), vec![Some(1); 3]);
+        assert_eq!(four.labels[(0, 2)], None);
+        let eight = grid.components(Connectivity::Eight, |&c| c == '#');
+        assert_eq!(eight.sizes, vec![3]);
+        assert_eq!(grid.components(Connectivity::Four, |&c| c == '#').sizes, vec![1, 1, 1]);
+    }
+
+    #[test]
+    fn points_index_by_x_and_y() {
+        let mut grid = numbered(2, 3);
+        let p = Point2D::new(2, 1);
+        assert_eq!(grid[p], 5);
+        assert_eq!(grid.get(p), Some(&5));
+        assert_eq!(grid.get(Point2D::new(1, 2)), None);
+        assert_eq!(grid.get((1, 2)), Some(&5));
+        *grid.get_mut(p).unwrap() = 9;
+        grid[Point2D::new(0, 0)] = 7;
+        assert_eq!(grid.get_mut((2, 0)), None);
+        let (pos, _) = grid.iter_points().find(|(_, &n)| n == 9).unwrap();
+        assert_eq!(pos, p);
+        let neighbours: Vec<_> = grid.neighbours4(p).map(|(pos, _)| pos).collect();
+        assert_eq!(neighbours, vec![Point2D::new(2, 0), Point2D::new(1, 1)]);
+        assert_eq!(grid.offset(p, (-1, -2)), Some(Point2D::new(0, 0)));
+    }
+
+    #[test]
+    fn growing_keeps_world_points() {
+        let mut grid = numbered(2, 2);
+        grid.pad(1, 9);
+        assert_eq!(grid.size(), (4, 4));
+        assert_eq!(grid.origin(), Point2D::new(-1, -1));
+        assert_eq!(grid.to_local(Point2D::new(1, 1)), Some((2, 2)));
+        assert_eq!(grid[(2, 2)], 3);
+        assert_eq!(grid.grow_to_include(Point2D::new(-3, 2), 8), (3, 0));
+        assert_eq!(grid.size(), (4, 6));
+        assert_eq!(grid.origin(), Point2D::new(-3, -1));
+        assert_eq!(grid.to_world((2, 4)), Point2D::new(1, 1));
+        assert_eq!(grid[(2, 4)], 3);
+        assert_eq!(grid.grow_to_include(Point2D::new(0, 0), 8), (1, 3));
+        assert_eq!(grid.size(), (4, 6));
+        grid.crop_to(|&n| n < 8);
+        assert_eq!(grid, numbered(2, 2));
+        grid.crop_to(|&n| n == 3);
+        assert_eq!((grid.size(), grid.origin()), ((1, 1), Point2D::new(1, 1)));
+        grid.crop_to(|_| false);
+        assert_eq!(grid.size(), (0, 0));
+        assert_eq!(grid.grow_to_include(Point2D::new(5, -5), 1), (0, 0));
+        assert_eq!((grid.size(), grid.origin()), ((1, 1), Point2D::new(5, -5)));
+    }
+
+    #[test]
+    fn derived_grids_keep_the_origin() {
+        let mut grid = numbered(2, 3);
+        grid.pad(1, 0);
+        let origin = Point2D::new(-1, -1);
+        assert_eq!(grid.map(|_, &n| n), grid);
+        assert_eq!(grid.convolve(&numbered(1, 1), |&n, _| n), grid);
+        assert_eq!(grid.components(Connectivity::Four, |&n| n > 0).labels.origin(), origin);
+        for transformed in [grid.transpose(), grid.rotate_cw(), grid.rotate_ccw(), grid.flip_h(), grid.flip_v()] {
+            assert_eq!(transformed.origin(), origin);
+        }
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(grid.view().to_grid().origin(), Point2D::new(0, 0));
+    }
+
+    #[test]
+    fn insert_rows_and_columns() {
+        let mut grid = numbered(2, 2);
+        grid.insert_row(1, 7);
+        grid.insert_row(3, 8);
+        grid.insert_col(0, 9);
+        assert_eq!(grid.size(), (4, 3));
+        assert_eq!(grid.rows().flatten().copied().collect::<Vec<_>>(), vec![9, 0, 1, 9, 7, 7, 9, 2, 3, 9, 8, 8]);
+        grid.insert_col(3, 6);
+        assert_eq!(grid.column(3).copied().collect::<Vec<_>>(), vec![6; 4]);
+        assert_eq!(grid.origin(), Point2D::new(0, 0));
+    }
+
+    #[test]
+    fn zero_width_grids() {
+        let mut grid: Grid<usize> = Grid::new(0, 0);
+        grid.insert_row(0, 1);
+        assert_eq!(grid.size(), (1, 0));
+        assert_eq!(grid.iter().count(), 0);
+        let mut grid: Grid<usize> = Grid::new(2, 0);
+        grid.crop_to(|_| true);
+        assert_eq!(grid.size(), (0, 0));
+        grid.insert_col(0, 5);
+        assert_eq!(grid.size(), (0, 1));
+        assert_eq!(grid.iter().count(), 0);
+    }
+
+    #[test]
+    fn windows_cover_each_fitting_position() {
+        let grid = numbered(3, 4);
+        let corners: Vec<_> = grid.windows(2, 3).map(|view| view[(0, 0)]).collect();
+        assert_eq!(corners, vec![0, 1, 4, 5]);
+        let last = grid.windows(2, 3).last().unwrap();
+        assert_eq!(last.iter().map(|(_, _, &n)| n).collect::<Vec<_>>(), vec![5, 6, 7, 9, 10, 11]);
+        assert_eq!(grid.windows(3, 4).count(), 1);
+        assert_eq!(grid.windows(4, 1).count(), 0);
+    }
+
+    #[test]
+    fn convolve_counts_neighbours() {
+        let blinker: Grid<bool> = Grid::from_fn(5, 5, |(y, x)| y == 2 && (1..=3).contains(&x));
+        let ring = Grid::from_fn(3, 3, |pos| pos != (1, 1));
+        let step = blinker.convolve(&ring, |&alive, cells| {
+            let n = cells.iter().filter(|(&k, &c)| k && c).count();
+            n == 3 || alive && n == 2
+        });
+        assert_eq!(step.to_string(), ".....\n..#..\n..#..\n..#..\n.....");
+        let sums = numbered(2, 3).convolve(&Grid::new_with_cells(vec![1; 9], 3, 3), |_, cells| {
+            cells.iter().map(|(&k, &n)| k * n).sum::<usize>()
+        });
+        assert_eq!(sums, Grid::new_with_cells(vec![8, 15, 12, 8, 15, 12], 2, 3));
+    }
+
+    #[test]
+    fn rays_run_to_the_edge() {
+        let grid = numbered(3, 4);
+        let right: Vec<_> = grid.ray((1, 1), (0, 1)).map(|(_, &n)| n).collect();
+        assert_eq!(right, vec![6, 7]);
+        let up_left: Vec<_> = grid.ray((2, 3), (-1, -1)).map(|(pos, _)| pos).collect();
+        assert_eq!(up_left, vec![(1, 2), (0, 1)]);
+        assert_eq!(grid.ray((0, 0), (-1, 0)).count(), 0);
+    }
+}
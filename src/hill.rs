@@ -27,14 +27,16 @@ impl Hill {
         &self.grid
     }
 
-    pub fn find_path(&self, start_pos: Coord, best: usize) -> Option<Vec<Coord>> {
+    /// Returns the first cell marked `c`, in reading order.
+    pub fn find(&self, c: char) -> Option<Coord> {
+        self.grid.iter_points().find(|(_, &cell)| cell == c).map(|(pos, _)| pos)
+    }
+
+    pub fn find_path(&self, start_pos: Coord, end_pos: Coord, best: usize) -> Option<Vec<Coord>> {
         let (rows, cols) = self.grid.size();
         let mut backtrack: Grid<Coord> = Grid::new(rows, cols);
         let mut queue: VecDeque<Coord> = VecDeque::new();
         let mut visited: HashSet<Coord> = HashSet::new();
-        let (y, x, _) = self.grid.iter().find(|(_, _, &c)| c == 'E')
-            .expect("end position not found");
-        let end_pos = Coord::new(x, y);
         queue.push_back(start_pos);
         visited.insert(start_pos);
         let mut len: usize = 0;
@@ -56,31 +58,16 @@ impl Hill {
             }
             at_cur_len -= 1;
 
-            let c = self.grid[(pos.y, pos.x)];
-
-            let mut neighbours: Vec<Coord> = Vec::new();
-            if pos.x > 0 {
-                neighbours.push(Coord::new(pos.x - 1, pos.y));
-            }
-            if pos.x + 1 < cols {
-                neighbours.push(Coord::new(pos.x + 1, pos.y));
-            }
-            if pos.y > 0 {
-                neighbours.push(Coord::new(pos.x, pos.y - 1));
-            }
-            if pos.y + 1 < rows {
-                neighbours.push(Coord::new(pos.x, pos.y + 1));
-            }
+            let c = self.grid[pos];
 
             let src_height = char_height(c);
-            for neighbour in neighbours {
+            for (neighbour, &dst) in self.grid.neighbours4(pos) {
                 if visited.contains(&neighbour) {
                     continue;
                 }
-                let dst = self.grid[(neighbour.y, neighbour.x)];
                 let dst_height = char_height(dst);
                 if src_height + 1 >= dst_height {
-                    backtrack[(neighbour.y, neighbour.x)] = pos;
+                    backtrack[neighbour] = pos;
                     queue.push_back(neighbour);
                     visited.insert(neighbour);
                     at_next_len += 1;
@@ -97,9 +84,8 @@ impl Hill {
             if pos == start_pos {
                 break;
             }
-            let (x, y) = (pos.x, pos.y);
             path.push(pos);
-            pos = backtrack[(y, x)];
+            pos = backtrack[pos];
         }
         Some(path)
     }
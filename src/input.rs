@@ -0,0 +1,449 @@
+use std::env;
+use std::error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::error::{Error, Span};
+use crate::grid::{Grid, ParseGridErrorKind};
+
+/// The name used in errors when input did not come from a file.
+pub const STR_SOURCE: &str = "<input>";
+
+#[derive(Debug)]
+pub enum InputErrorKind {
+    Io(io::Error),
+    Empty,
+    NotANumber,
+    NotADigit(char),
+    InconsistentWidth { expected: usize, found: usize },
+}
+
+/// An error reading puzzle input. Lines and columns are 1-based, and the
+/// column is the byte offset of the first bad byte on the line. Both are zero
+/// when the error is not tied to a position, such as a missing file.
+#[derive(Debug)]
+pub struct InputError {
+    pub source: String,
+    pub line: usize,
+    pub column: usize,
+    pub kind: InputErrorKind,
+}
+
+impl InputError {
+    fn new(source: &str, line: usize, column: usize, kind: InputErrorKind) -> Self {
+        Self {
+            source: source.to_owned(),
+            line,
+            column,
+            kind,
+        }
+    }
+
+    fn io(source: &str, err: io::Error) -> Self {
+        Self::new(source, 0, 0, InputErrorKind::Io(err))
+    }
+}
+
+impl fmt::Display for InputErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{}", err),
+            Self::Empty => write!(f, "no input"),
+            Self::NotANumber => write!(f, "expected an unsigned integer"),
+            Self::NotADigit(c) => write!(f, "expected a digit, found {:?}", c),
+            Self::InconsistentWidth { expected, found } => {
+                write!(f, "expected a row of width {}, found {}", expected, found)
+            }
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)?;
+        if self.line > 0 {
+            write!(f, ":{}:{}", self.line, self.column)?;
+        }
+        write!(f, ": {}", self.kind)
+    }
+}
+
+impl error::Error for InputError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match &self.kind {
+            InputErrorKind::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// The name used in errors when input came from an unnamed reader.
+pub const READER_SOURCE: &str = "<reader>";
+
+/// The name used in errors when input is read from standard input.
+pub const STDIN_SOURCE: &str = "<stdin>";
+
+/// The environment variable naming the directory that holds puzzle inputs.
+/// Inputs are looked up in the current directory when it is not set.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Which of a day's input files to read.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum InputKind {
+    /// The real puzzle input, `NN_input.txt`.
+    #[default]
+    Real,
+    /// A sample input. Variant 1 is `NN_test.txt` and variant `n` is
+    /// `NN_test_n.txt`.
+    Test(usize),
+}
+
+impl InputKind {
+    pub fn file_name(&self, day: usize) -> String {
+        match self {
+            Self::Real => format!("{:02}_input.txt", day),
+            Self::Test(1) => format!("{:02}_test.txt", day),
+            Self::Test(n) => format!("{:02}_test_{}.txt", day, n),
+        }
+    }
+}
+
+/// Returns the path of a day's input, inside `AOC_INPUT_DIR` if it is set.
+pub fn day_input_path(day: usize, kind: InputKind) -> String {
+    let name = kind.file_name(day);
+    match env::var_os(INPUT_DIR_VAR) {
+        Some(dir) => Path::new(&dir).join(name).to_string_lossy().into_owned(),
+        None => name,
+    }
+}
+
+/// Opens a path for reading, treating `-` as standard input.
+pub fn open(path: &str) -> Result<Box<dyn BufRead>, InputError> {
+    if path == "-" {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    let f = File::open(path).map_err(|err| InputError::io(path, err))?;
+    Ok(Box::new(BufReader::new(f)))
+}
+
+/// Returns the name to report errors against for a path, which is
+/// [`STDIN_SOURCE`] for `-`.
+pub fn source_name(path: &str) -> &str {
+    if path == "-" { STDIN_SOURCE } else { path }
+}
+
+fn lines<'a, R: BufRead + 'a>(
+    reader: R,
+    source: &'a str,
+) -> impl Iterator<Item = Result<(usize, String), InputError>> + 'a {
+    reader.lines().enumerate().map(move |(i, line)| {
+        line.map(|line| (i + 1, line))
+            .map_err(|err| InputError::new(source, i + 1, 1, InputErrorKind::Io(err)))
+    })
+}
+
+fn parse_uint(source: &str, line_no: usize, line: &str) -> Result<usize, InputError> {
+    if let Some(col) = line.bytes().position(|b| !b.is_ascii_digit()) {
+        return Err(InputError::new(source, line_no, col + 1, InputErrorKind::NotANumber));
+    }
+    // Only overflow or an empty line can fail once every byte is a digit
+    line.parse()
+        .map_err(|_| InputError::new(source, line_no, 1, InputErrorKind::NotANumber))
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// An iterator over the blocks of a string, as returned by [`blocks`].
+pub struct Blocks<'a> {
+    rest: &'a str,
+    line: usize,
+}
+
+fn split_line(s: &str) -> (&str, &str) {
+    match s.find('\n') {
+        Some(i) => (&s[..i], &s[i + 1..]),
+        None => (s, ""),
+    }
+}
+
+impl<'a> Iterator for Blocks<'a> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.rest.is_empty() {
+                return None;
+            }
+            let (line, rest) = split_line(self.rest);
+            if !is_blank(line) {
+                break;
+            }
+            self.rest = rest;
+            self.line += 1;
+        }
+        let start = self.line;
+        let mut len = 0;
+        let mut rest = self.rest;
+        while !rest.is_empty() {
+            let (line, after) = split_line(rest);
+            if is_blank(line) {
+                break;
+            }
+            len = self.rest.len() - after.len();
+            rest = after;
+            self.line += 1;
+        }
+        let block = self.rest[..len].trim_end_matches(['\n', '\r']);
+        self.rest = rest;
+        Some((start, block))
+    }
+}
+
+/// Splits a string into blocks of lines separated by one or more blank
+/// lines, yielding the 1-based line each block starts on and its text
+/// without the final newline. Lines holding only whitespace count as blank.
+pub fn blocks(input: &str) -> Blocks<'_> {
+    Blocks { rest: input, line: 1 }
+}
+
+fn grid<R, T, F>(reader: R, source: &str, cell: F) -> Result<Grid<T>, InputError>
+where
+    R: BufRead,
+    F: FnMut(char, (usize, usize)) -> Result<T, InputErrorKind>,
+{
+    let input = try_read_input_from(reader, source)?;
+    Grid::parse_with(&input, cell).map_err(|err| {
+        let kind = match err.kind {
+            ParseGridErrorKind::Cell(kind) => kind,
+            ParseGridErrorKind::InconsistentWidth { expected, found } => {
+                InputErrorKind::InconsistentWidth { expected, found }
+            }
+        };
+        InputError::new(source, err.line, err.column, kind)
+    })
+}
+
+pub fn try_read_input_from<R: BufRead>(mut reader: R, source: &str) -> Result<String, InputError> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input).map_err(|err| InputError::io(source, err))?;
+    Ok(input)
+}
+
+pub fn try_read_lines_from<R: BufRead>(reader: R, source: &str) -> Result<Vec<String>, InputError> {
+    lines(reader, source).map(|line| line.map(|(_, line)| line)).collect()
+}
+
+pub fn try_read_line_from<R: BufRead>(reader: R, source: &str) -> Result<String, InputError> {
+    let (_, line) = lines(reader, source).next()
+        .ok_or_else(|| InputError::new(source, 0, 0, InputErrorKind::Empty))??;
+    Ok(line)
+}
+
+pub fn try_read_uints_from<R: BufRead>(reader: R, source: &str) -> Result<Vec<usize>, InputError> {
+    lines(reader, source)
+        .map(|line| line.and_then(|(line_no, line)| parse_uint(source, line_no, &line)))
+        .collect()
+}
+
+/// Iterates over the lines of each of [`blocks`], numbered from 1 within the
+/// whole input.
+fn numbered_blocks(input: &str) -> impl Iterator<Item = impl Iterator<Item = (usize, &str)>> {
+    blocks(input).map(|(start, block)| block.lines().enumerate().map(move |(i, line)| (start + i, line)))
+}
+
+pub fn try_read_blocks_from<R: BufRead>(reader: R, source: &str) -> Result<Vec<Vec<String>>, InputError> {
+    let input = try_read_input_from(reader, source)?;
+    Ok(numbered_blocks(&input)
+        .map(|block| block.map(|(_, line)| line.to_string()).collect())
+        .collect())
+}
+
+pub fn try_read_uint_lists_from<R: BufRead>(reader: R, source: &str) -> Result<Vec<Vec<usize>>, InputError> {
+    let input = try_read_input_from(reader, source)?;
+    numbered_blocks(&input)
+        .map(|block| block.map(|(line_no, line)| parse_uint(source, line_no, line)).collect())
+        .collect()
+}
+
+pub fn try_read_uint_grid_from<R: BufRead>(reader: R, source: &str) -> Result<Grid<usize>, InputError> {
+    grid(reader, source, |c, _| c.to_digit(10).map(|d| d as usize).ok_or(InputErrorKind::NotADigit(c)))
+}
+
+pub fn try_read_char_grid_from<R: BufRead>(reader: R, source: &str) -> Result<Grid<char>, InputError> {
+    grid(reader, source, |c, _| Ok(c))
+}
+
+/// Parses every line of a string as a `T`, placing any error on its line.
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>, Error>
+where
+    T: FromStr,
+    Error: From<T::Err>,
+{
+    input.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            line.parse::<T>().map_err(|err| Error::from(err).at(Span::new(i + 1, 1, line.len())))
+        })
+        .collect()
+}
+
+/// Parses every block of a string as a `T`, placing any error within its
+/// block. Errors without a location are placed on the block's first line.
+pub fn parse_blocks<T>(input: &str) -> Result<Vec<T>, Error>
+where
+    T: FromStr,
+    Error: From<T::Err>,
+{
+    blocks(input)
+        .map(|(line, block)| {
+            block.parse::<T>().map_err(|err| Error::from(err).shifted(line - 1).at_line(line))
+        })
+        .collect()
+}
+
+pub fn try_read_input(path: &str) -> Result<String, InputError> {
+    try_read_input_from(open(path)?, source_name(path))
+}
+
+pub fn try_read_lines(path: &str) -> Result<Vec<String>, InputError> {
+    try_read_lines_from(open(path)?, source_name(path))
+}
+
+pub fn try_read_line(path: &str) -> Result<String, InputError> {
+    try_read_line_from(open(path)?, source_name(path))
+}
+
+pub fn try_read_uints(path: &str) -> Result<Vec<usize>, InputError> {
+    try_read_uints_from(open(path)?, source_name(path))
+}
+
+pub fn try_read_blocks(path: &str) -> Result<Vec<Vec<String>>, InputError> {
+    try_read_blocks_from(open(path)?, source_name(path))
+}
+
+pub fn try_read_uint_lists(path: &str) -> Result<Vec<Vec<usize>>, InputError> {
+    try_read_uint_lists_from(open(path)?, source_name(path))
+}
+
+pub fn try_read_uint_grid(path: &str) -> Result<Grid<usize>, InputError> {
+    try_read_uint_grid_from(open(path)?, source_name(path))
+}
+
+pub fn try_read_char_grid(path: &str) -> Result<Grid<char>, InputError> {
+    try_read_char_grid_from(open(path)?, source_name(path))
+}
+
+pub fn try_parse_uints(input: &str) -> Result<Vec<usize>, InputError> {
+    try_read_uints_from(input.as_bytes(), STR_SOURCE)
+}
+
+pub fn try_parse_uint_lists(input: &str) -> Result<Vec<Vec<usize>>, InputError> {
+    try_read_uint_lists_from(input.as_bytes(), STR_SOURCE)
+}
+
+pub fn try_parse_uint_grid(input: &str) -> Result<Grid<usize>, InputError> {
+    try_read_uint_grid_from(input.as_bytes(), STR_SOURCE)
+}
+
+pub fn try_parse_char_grid(input: &str) -> Result<Grid<char>, InputError> {
+    try_read_char_grid_from(input.as_bytes(), STR_SOURCE)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::{Error, Span};
+    use super::{
+        blocks, parse_blocks, try_parse_char_grid, try_parse_uint_grid, try_parse_uint_lists,
+        try_parse_uints, try_read_blocks_from, try_read_lines_from, InputErrorKind, InputKind,
+    };
+
+    #[test]
+    fn input_file_names() {
+        assert_eq!(InputKind::Real.file_name(9), "09_input.txt");
+        assert_eq!(InputKind::Test(1).file_name(9), "09_test.txt");
+        assert_eq!(InputKind::Test(2).file_name(9), "09_test_2.txt");
+    }
+
+    #[test]
+    fn uints_report_first_bad_byte() {
+        let err = try_parse_uints("12\n3x4\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert!(matches!(err.kind, InputErrorKind::NotANumber));
+        assert_eq!(err.to_string(), "<input>:2:2: expected an unsigned integer");
+    }
+
+    #[test]
+    fn uint_lists_split_on_blank_lines() {
+        let lists = try_parse_uint_lists("1\n2\n\n3\n").unwrap();
+        assert_eq!(lists, vec![vec![1, 2], vec![3]]);
+        let err = try_parse_uint_lists("1\n\n-3\n").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+        let err = try_parse_uint_lists("1\r\n \r\n\r\n2\r\n4x\r\n").unwrap_err();
+        assert_eq!((err.line, err.column), (5, 2));
+    }
+
+    #[test]
+    fn blocks_skip_runs_of_blank_lines() {
+        let input = "\n\na\nb\r\n\n \n\nc\n\n";
+        assert_eq!(blocks(input).collect::<Vec<_>>(), vec![(3, "a\nb"), (8, "c")]);
+        assert_eq!(blocks("").count(), 0);
+        assert_eq!(blocks("x").collect::<Vec<_>>(), vec![(1, "x")]);
+        let read = try_read_blocks_from(input.as_bytes(), "bytes").unwrap();
+        assert_eq!(read, vec![vec!["a", "b"], vec!["c"]]);
+    }
+
+    #[test]
+    fn parse_blocks_places_errors_in_the_input() {
+        let lists: Vec<Vec<usize>> = parse_blocks::<Numbers>("1\n2\n\n3\n").unwrap()
+            .into_iter()
+            .map(|numbers| numbers.0)
+            .collect();
+        assert_eq!(lists, vec![vec![1, 2], vec![3]]);
+        let err = parse_blocks::<Numbers>("1\n\n\n2\nx\n").unwrap_err();
+        assert_eq!(err.span(), Some(Span::new(5, 1, 1)));
+        assert_eq!(parse_blocks::<Numbers>("1\n\n\n").unwrap().len(), 1);
+    }
+
+    #[derive(Debug)]
+    struct Numbers(Vec<usize>);
+
+    impl std::str::FromStr for Numbers {
+        type Err = Error;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(Self(super::try_parse_uints(s)?))
+        }
+    }
+
+    #[test]
+    fn uint_grid_reports_non_digit() {
+        let err = try_parse_uint_grid("123\n4a6\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert!(matches!(err.kind, InputErrorKind::NotADigit('a')));
+    }
+
+    #[test]
+    fn grid_reports_ragged_rows() {
+        let err = try_parse_char_grid("abc\nabcd\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+        assert!(matches!(err.kind, InputErrorKind::InconsistentWidth { expected: 3, found: 4 }));
+        let err = try_parse_char_grid("abc\nab\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert!(matches!(err.kind, InputErrorKind::InconsistentWidth { expected: 3, found: 2 }));
+    }
+
+    #[test]
+    fn lines_from_any_reader() {
+        let reader = std::io::Cursor::new(b"a\r\nb\n".to_vec());
+        let lines = try_read_lines_from(reader, "cursor").unwrap();
+        assert_eq!(lines, vec!["a", "b"]);
+        let err = try_read_lines_from(&b"ok\n\xff\n"[..], "bytes").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(matches!(err.kind, InputErrorKind::Io(_)));
+    }
+}
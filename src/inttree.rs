@@ -1,5 +1,6 @@
 use std::cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd};
-
+use std::error;
+use std::fmt;
 use std::str::FromStr;
 
 #[derive(Debug, Copy, Clone)]
@@ -10,6 +11,19 @@ pub enum ParseError {
     EmptyString,
 }
 
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCharacter => write!(f, "unexpected character in packet"),
+            Self::UnbalancedList => write!(f, "unbalanced brackets in packet"),
+            Self::InvalidNumber => write!(f, "invalid number in packet"),
+            Self::EmptyString => write!(f, "empty packet"),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
 pub enum UintNode {
     Leaf(usize),
     Tree(Vec<UintNode>),
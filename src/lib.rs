@@ -1,95 +1,139 @@
 use std::env;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fmt;
+use std::io::BufRead;
+use std::result;
 
 pub mod algo;
+pub mod answer;
 pub mod assignment;
+pub mod bitgrid;
+pub mod bench;
 pub mod cave;
 pub mod cli;
 pub mod comms;
 pub mod cpu;
 pub mod crane;
+pub mod days;
+pub mod direction;
+pub mod error;
 pub mod grid;
 pub mod hill;
+pub mod input;
 pub mod inttree;
 pub mod monkey;
+pub mod netpbm;
+pub mod output;
 pub mod point;
 pub mod rps;
 pub mod rucksack;
+pub mod sight;
 pub mod snake;
+pub mod sparse;
 
-pub fn input_arg() -> String {
-    env::args()
-        .nth(1)
-        .unwrap_or_else(|| "input.txt".to_string())
+pub use error::{Error, Result};
+
+/// Returns the input path for a binary that solves `day`. The arguments may
+/// name a path, where `-` means standard input, or pick the day's sample
+/// with `--test` or `--test-variant N`. Otherwise the day's real input is
+/// used.
+pub fn day_input_arg(day: usize) -> String {
+    let mut kind = input::InputKind::Real;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--test" => kind = input::InputKind::Test(1),
+            "--test-variant" => {
+                let variant = args.next().and_then(|n| n.parse().ok());
+                kind = input::InputKind::Test(or_exit(variant.ok_or("--test-variant requires a number")));
+            }
+            _ => return arg,
+        }
+    }
+    input::day_input_path(day, kind)
+}
+
+/// Unwraps a result, or reports the error and exits.
+pub fn or_exit<T, E: fmt::Display>(result: result::Result<T, E>) -> T {
+    result.unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    })
+}
+
+fn or_panic<T>(result: result::Result<T, input::InputError>) -> T {
+    result.unwrap_or_else(|err| panic!("{}", err))
+}
+
+pub fn read_input(path: &str) -> String {
+    or_panic(input::try_read_input(path))
 }
 
 pub fn read_line(path: &str) -> String {
-    read_lines(path).next().expect("No lines of input")
+    or_panic(input::try_read_line(path))
 }
 
 pub fn read_lines(path: &str) -> impl Iterator<Item = String> {
-    let f = File::open(path).expect("Unable to read input file");
-    let reader = BufReader::new(f);
-    reader
-        .lines()
-        .into_iter()
-        .map(|x| x.expect("Unable to read input line"))
+    or_panic(input::try_read_lines(path)).into_iter()
+}
+
+pub fn read_lines_from<R: BufRead>(reader: R) -> impl Iterator<Item = String> {
+    or_panic(input::try_read_lines_from(reader, input::READER_SOURCE)).into_iter()
 }
 
 pub fn read_uints(path: &str) -> impl Iterator<Item = usize> {
-    read_lines(path).map(|x| {
-        x.parse::<usize>()
-            .expect("Line was not an unsigned integer")
-    })
+    or_panic(input::try_read_uints(path)).into_iter()
+}
+
+pub fn read_uints_from<R: BufRead>(reader: R) -> impl Iterator<Item = usize> {
+    or_panic(input::try_read_uints_from(reader, input::READER_SOURCE)).into_iter()
+}
+
+pub fn parse_uints(input: &str) -> impl Iterator<Item = usize> {
+    or_panic(input::try_parse_uints(input)).into_iter()
+}
+
+/// Returns the blocks of lines in a file, where blocks are separated by one
+/// or more blank lines.
+pub fn read_blocks(path: &str) -> impl Iterator<Item = Vec<String>> {
+    or_panic(input::try_read_blocks(path)).into_iter()
+}
+
+pub fn read_blocks_from<R: BufRead>(reader: R) -> impl Iterator<Item = Vec<String>> {
+    or_panic(input::try_read_blocks_from(reader, input::READER_SOURCE)).into_iter()
 }
 
 pub fn read_uint_lists(path: &str) -> Vec<Vec<usize>> {
-    let vecs: Vec<Vec<usize>> = vec![vec![]];
-    read_lines(path).fold(vecs, |mut vecs, line| {
-        if line.is_empty() {
-            vecs.push(Vec::new());
-        } else {
-            let n = line.parse()
-                .expect("Line was not an unsigned integer");
-            vecs.last_mut().unwrap().push(n);
-        };
-        vecs
-    })
+    or_panic(input::try_read_uint_lists(path))
+}
+
+pub fn read_uint_lists_from<R: BufRead>(reader: R) -> Vec<Vec<usize>> {
+    or_panic(input::try_read_uint_lists_from(reader, input::READER_SOURCE))
+}
+
+pub fn parse_uint_lists(input: &str) -> Vec<Vec<usize>> {
+    or_panic(input::try_parse_uint_lists(input))
 }
 
 pub fn read_uint_grid(path: &str) -> grid::Grid<usize> {
-    let mut rows = 0;
-    let mut cols = 0;
-    let mut cells: Vec<usize> = Vec::new();
-    read_lines(path).for_each(|line| {
-        rows += 1;
-        if cols == 0 {
-            cols = line.len();
-        } else if cols != line.len() {
-            panic!("inconsistent grid width");
-        }
-        for c in line.chars() {
-            let d = c.to_digit(10).expect("not a digit") as usize;
-            cells.push(d);
-        }
-    });
-    grid::Grid::new_with_cells(cells, rows, cols)
+    or_panic(input::try_read_uint_grid(path))
+}
+
+pub fn read_uint_grid_from<R: BufRead>(reader: R) -> grid::Grid<usize> {
+    or_panic(input::try_read_uint_grid_from(reader, input::READER_SOURCE))
+}
+
+pub fn parse_uint_grid(input: &str) -> grid::Grid<usize> {
+    or_panic(input::try_parse_uint_grid(input))
 }
 
 pub fn read_char_grid(path: &str) -> grid::Grid<char> {
-    let mut rows = 0;
-    let mut cols = 0;
-    let mut cells: Vec<char> = Vec::new();
-    read_lines(path).for_each(|line| {
-        rows += 1;
-        let mut cs: Vec<char> = line.chars().collect();
-        if cols == 0 {
-            cols = cs.len();
-        } else if cols != cs.len() {
-            panic!("inconsistent grid width");
-        }
-        cells.append(&mut cs);
-    });
-    grid::Grid::new_with_cells(cells, rows, cols)
+    or_panic(input::try_read_char_grid(path))
+}
+
+pub fn read_char_grid_from<R: BufRead>(reader: R) -> grid::Grid<char> {
+    or_panic(input::try_read_char_grid_from(reader, input::READER_SOURCE))
+}
+
+pub fn parse_char_grid(input: &str) -> grid::Grid<char> {
+    or_panic(input::try_parse_char_grid(input))
 }
@@ -0,0 +1,243 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process;
+use std::time::Instant;
+
+use aoc::bench::{self, Record, DEFAULT_RUNS};
+use aoc::days::{self, Part, Solution};
+use aoc::input::{day_input_path, source_name, try_read_input, InputKind};
+use aoc::output::{Format, Report};
+use aoc::Error;
+
+const USAGE: &str = "\
+usage: aoc run <day> [a|b] [--input <path> | --test | --test-variant <n>]
+               [--format plain|json|tsv]
+       aoc run --all [--test | --test-variant <n>] [--format plain|json|tsv]
+       aoc bench <day> [a|b] [--input <path> | --test | --test-variant <n>]
+                 [--runs <n>] [--save <path.json>]
+       aoc bench --all [--test | --test-variant <n>] [--runs <n>] [--save <path.json>]
+
+Inputs default to NN_input.txt, or NN_test.txt with --test, in the current
+directory or in $AOC_INPUT_DIR when it is set.
+
+--format json writes one {day, part, answer, elapsed} object per line, with
+elapsed in seconds and multi-line answers as arrays of rows. --format tsv
+writes the same fields as tab-separated values after a header line.
+
+bench times the parse and solve phases separately over a number of runs
+(default 20) and reports the min, median and max of each. --save writes the
+timings as JSON, one record per line, so two runs can be diffed.";
+
+#[derive(Default)]
+struct RunArgs {
+    day: Option<usize>,
+    part: Option<Part>,
+    input: Option<String>,
+    kind: Option<InputKind>,
+    all: bool,
+    runs: Option<usize>,
+    save: Option<String>,
+    format: Format,
+}
+
+impl RunArgs {
+    /// Parses the arguments to `run`, or to `bench` when `bench` is set,
+    /// which accepts `--runs` and `--save` instead of `--format`.
+    fn parse<I: Iterator<Item = String>>(mut args: I, bench: bool) -> Result<Self, String> {
+        let mut run = Self::default();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--all" => run.all = true,
+                "--input" => {
+                    let path = args.next().ok_or("--input requires a path")?;
+                    run.input = Some(path);
+                }
+                "--test" => run.kind = Some(InputKind::Test(1)),
+                "--test-variant" => {
+                    let variant = args.next().and_then(|n| n.parse().ok())
+                        .ok_or("--test-variant requires a number")?;
+                    run.kind = Some(InputKind::Test(variant));
+                }
+                "--format" if !bench => {
+                    let format = args.next().ok_or("--format requires a format")?;
+                    run.format = format.parse().map_err(|err| format!("{}: {}", format, err))?;
+                }
+                "--runs" if bench => {
+                    let runs = args.next().and_then(|n| n.parse().ok()).filter(|&n| n > 0)
+                        .ok_or("--runs requires a positive number")?;
+                    run.runs = Some(runs);
+                }
+                "--save" if bench => {
+                    let path = args.next().ok_or("--save requires a path")?;
+                    run.save = Some(path);
+                }
+                _ if arg.starts_with("--") => return Err(format!("unexpected argument: {}", arg)),
+                _ if run.day.is_none() => {
+                    let day = arg.parse().map_err(|_| format!("invalid day: {}", arg))?;
+                    run.day = Some(day);
+                }
+                _ if run.part.is_none() => {
+                    let part = arg.parse().map_err(|_| format!("invalid part: {}", arg))?;
+                    run.part = Some(part);
+                }
+                _ => return Err(format!("unexpected argument: {}", arg)),
+            }
+        }
+        if run.all == run.day.is_some() {
+            return Err("expected either a day or --all".to_string());
+        }
+        if run.input.is_some() && (run.all || run.kind.is_some()) {
+            return Err("--input only applies to a single day without --test".to_string());
+        }
+        Ok(run)
+    }
+}
+
+/// Runs one solution and reports its answer or error. Returns whether it
+/// succeeded.
+fn run_solution(solution: &Solution, path: &str, input: &str, format: Format) -> bool {
+    let start = Instant::now();
+    match (solution.solve)(input) {
+        Ok(answer) => {
+            let report = Report {
+                day: solution.day,
+                part: solution.part,
+                answer: &answer,
+                elapsed: start.elapsed(),
+            };
+            println!("{}", format.report(&report));
+            true
+        }
+        Err(err) => {
+            eprintln!("Day {:02}{}: {}", solution.day, solution.part, err.in_source(source_name(path)));
+            false
+        }
+    }
+}
+
+fn check_failures(failed: usize, total: usize) -> Result<(), String> {
+    if failed > 0 {
+        return Err(format!("{} of {} solutions failed", failed, total));
+    }
+    Ok(())
+}
+
+fn run_all(kind: InputKind, format: Format) -> Result<(), String> {
+    let mut failed = 0;
+    for solution in days::SOLUTIONS {
+        let path = day_input_path(solution.day, kind);
+        if !Path::new(&path).exists() {
+            eprintln!("Day {:02}{}: skipped, {} not found", solution.day, solution.part, path);
+            continue;
+        }
+        let input = try_read_input(&path).map_err(|err| Error::from(err).to_string())?;
+        if !run_solution(solution, &path, &input, format) {
+            failed += 1;
+        }
+    }
+    check_failures(failed, days::SOLUTIONS.len())
+}
+
+/// Picks the solutions a single-day command applies to.
+fn day_solutions(day: usize, part: Option<Part>) -> Result<Vec<&'static Solution>, String> {
+    let solutions: Vec<&Solution> = match part {
+        Some(part) => days::find(day, part).into_iter().collect(),
+        None => days::for_day(day).collect(),
+    };
+    if solutions.is_empty() {
+        return Err(format!("no solution registered for day {}", day));
+    }
+    Ok(solutions)
+}
+
+fn run(args: RunArgs) -> Result<(), String> {
+    let kind = args.kind.unwrap_or_default();
+    if let Some(header) = args.format.header() {
+        println!("{}", header);
+    }
+    if args.all {
+        return run_all(kind, args.format);
+    }
+    let day = args.day.expect("day was checked when parsing");
+    let path = args.input.unwrap_or_else(|| day_input_path(day, kind));
+    let solutions = day_solutions(day, args.part)?;
+    let input = try_read_input(&path).map_err(|err| Error::from(err).to_string())?;
+    let failed = solutions.iter()
+        .filter(|solution| !run_solution(solution, &path, &input, args.format))
+        .count();
+    check_failures(failed, solutions.len())
+}
+
+fn print_record(record: &Record) {
+    let label = format!("Day {:02}{}", record.day, record.part);
+    for (phase, stats) in [("parse", record.parse), ("solve", record.solve)] {
+        println!(
+            "{:8} {}  min {:>10.1?}  median {:>10.1?}  max {:>10.1?}",
+            label, phase, stats.min, stats.median, stats.max,
+        );
+    }
+}
+
+/// Benchmarks each solution against its input, printing each record as it
+/// is measured. Returns the records and the number of solutions that failed.
+fn bench_solutions(solutions: &[(&Solution, String)], runs: usize) -> (Vec<Record>, usize) {
+    let mut records = Vec::new();
+    let mut failed = 0;
+    for (solution, path) in solutions {
+        let result = try_read_input(path)
+            .map_err(Error::from)
+            .and_then(|input| bench::bench(solution, &input, runs));
+        match result {
+            Ok(record) => {
+                print_record(&record);
+                records.push(record);
+            }
+            Err(err) => {
+                eprintln!("Day {:02}{}: {}", solution.day, solution.part, err.in_source(source_name(path)));
+                failed += 1;
+            }
+        }
+    }
+    (records, failed)
+}
+
+fn bench(args: RunArgs) -> Result<(), String> {
+    let kind = args.kind.unwrap_or_default();
+    let solutions: Vec<(&Solution, String)> = if args.all {
+        days::SOLUTIONS.iter()
+            .map(|solution| (solution, day_input_path(solution.day, kind)))
+            .filter(|(solution, path)| {
+                let found = Path::new(path).exists();
+                if !found {
+                    eprintln!("Day {:02}{}: skipped, {} not found", solution.day, solution.part, path);
+                }
+                found
+            })
+            .collect()
+    } else {
+        let day = args.day.expect("day was checked when parsing");
+        let path = args.input.unwrap_or_else(|| day_input_path(day, kind));
+        day_solutions(day, args.part)?.into_iter()
+            .map(|solution| (solution, path.clone()))
+            .collect()
+    };
+    let (records, failed) = bench_solutions(&solutions, args.runs.unwrap_or(DEFAULT_RUNS));
+    if let Some(path) = args.save {
+        fs::write(&path, bench::to_json(&records)).map_err(|err| format!("{}: {}", path, err))?;
+    }
+    check_failures(failed, solutions.len())
+}
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let result = match args.next().as_deref() {
+        Some("run") => RunArgs::parse(args, false).and_then(run),
+        Some("bench") => RunArgs::parse(args, true).and_then(bench),
+        _ => Err(USAGE.to_string()),
+    };
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
@@ -1,7 +1,10 @@
 use std::collections::VecDeque;
+use std::error;
+use std::fmt;
 use std::str::FromStr;
 
 use crate::algo::lcm;
+use crate::input::blocks;
 use regex::Regex;
 
 #[derive(Debug, Copy, Clone)]
@@ -15,6 +18,22 @@ pub enum ParseError {
     BranchMatchFailed,
 }
 
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidNumber => write!(f, "invalid number"),
+            Self::InvalidOperation => write!(f, "unknown operator"),
+            Self::MonkeyMatchFailed => write!(f, "expected \"Monkey N:\""),
+            Self::StartingMatchFailed => write!(f, "expected \"Starting items: ...\""),
+            Self::OperationMatchFailed => write!(f, "expected \"Operation: new = ...\""),
+            Self::TestMatchFailed => write!(f, "expected \"Test: divisible by N\""),
+            Self::BranchMatchFailed => write!(f, "expected \"If true/false: throw to monkey N\""),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
 #[derive(Debug, Copy, Clone)]
 pub enum Operand {
     Old,
@@ -84,7 +103,7 @@ pub struct Decision {
 
 impl Decision {
     pub fn decide(&self, item: usize) -> usize {
-        if item % self.div_by == 0 {
+        if item.is_multiple_of(self.div_by) {
             self.if_true
         } else {
             self.if_false
@@ -127,6 +146,7 @@ pub struct Transfer {
     pub item: usize,
 }
 
+#[derive(Clone)]
 pub struct Monkey {
     items: VecDeque<usize>,
     op: Operation,
@@ -203,12 +223,20 @@ impl FromStr for Monkey {
     }
 }
 
+#[derive(Clone)]
 pub struct Monkeys {
     monkeys: Vec<Monkey>,
 }
 
 impl Monkeys {
-    pub fn new(monkeys: Vec<Monkey>) -> Self {
+    /// Groups monkeys so that they can throw items to each other. Worry
+    /// levels are kept modulo the least common multiple of the monkeys'
+    /// divisors, which leaves every test result unchanged.
+    pub fn new(mut monkeys: Vec<Monkey>) -> Self {
+        let lcm = monkeys.iter().map(|m| m.decision.div_by).reduce(lcm).unwrap_or(0);
+        for monkey in monkeys.iter_mut() {
+            monkey.lcm = lcm;
+        }
         Self { monkeys }
     }
 
@@ -235,21 +263,9 @@ impl FromStr for Monkeys {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.split('\n');
-        let mut monkeys: Vec<Monkey> = Vec::new();
-        loop {
-            let group: Vec<_> = (&mut lines).take(7).collect();
-            if group.len() < 6 {
-                break;
-            }
-            let monkey: Monkey = group.join("\n").parse()?;
-            monkeys.push(monkey);
-        }
-        let lcm_vals = monkeys.iter().map(|m| m.decision.div_by);
-        let lcm = lcm_vals.reduce(lcm).expect("Not enough monkeys");
-        for monkey in monkeys.iter_mut() {
-            monkey.lcm = lcm;
-        }
+        let monkeys = blocks(s)
+            .map(|(_, block)| block.parse())
+            .collect::<Result<Vec<Monkey>, _>>()?;
         Ok(Self::new(monkeys))
     }
 }
@@ -0,0 +1,415 @@
+//! Reading and writing grids as netpbm images: PBM bitmaps (P1 and P4), PGM
+//! graymaps (P2 and P5) and PPM pixmaps (P3 and P6).
+
+use std::error;
+use std::fmt;
+use std::io::{self, Read, Write};
+
+use crate::grid::Grid;
+
+/// The longest line the ASCII formats write, as the netpbm spec asks.
+const MAX_LINE: usize = 70;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Encoding {
+    /// The plain formats, P1, P2 and P3, with samples written as decimal
+    /// text.
+    Ascii,
+    /// The raw formats, P4, P5 and P6.
+    Binary,
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+    Io(io::Error),
+    UnknownFormat,
+    InvalidHeader,
+    /// A maximum sample value outside 1 to 65535.
+    InvalidMaxval(usize),
+    SampleTooLarge { sample: usize, maxval: u16 },
+    Truncated,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{}", err),
+            Self::UnknownFormat => write!(f, "expected a netpbm magic number from P1 to P6"),
+            Self::InvalidHeader => write!(f, "expected a width, height and maximum value"),
+            Self::InvalidMaxval(n) => write!(f, "maximum value {} is not between 1 and 65535", n),
+            Self::SampleTooLarge { sample, maxval } => write!(f, "sample {} is over the maximum of {}", sample, maxval),
+            Self::Truncated => write!(f, "image data ends early"),
+        }
+    }
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// A decoded image. Samples keep the scale of the file they came from, up to
+/// `maxval`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Image {
+    /// Black pixels are `true`.
+    Bitmap(Grid<bool>),
+    Graymap { maxval: u16, pixels: Grid<u16> },
+    Pixmap { maxval: u16, pixels: Grid<[u16; 3]> },
+}
+
+/// Writes ASCII samples separated by spaces, starting a new line for each
+/// row or when a line would get too long.
+struct AsciiWriter<W> {
+    w: W,
+    line_len: usize,
+}
+
+impl<W: Write> AsciiWriter<W> {
+    fn sample(&mut self, sample: &str, separated: bool) -> io::Result<()> {
+        let sep = usize::from(separated && self.line_len > 0);
+        if self.line_len > 0 && self.line_len + sep + sample.len() > MAX_LINE {
+            self.end_line()?;
+        } else if sep > 0 {
+            self.w.write_all(b" ")?;
+            self.line_len += 1;
+        }
+        self.w.write_all(sample.as_bytes())?;
+        self.line_len += sample.len();
+        Ok(())
+    }
+
+    fn end_line(&mut self) -> io::Result<()> {
+        self.w.write_all(b"\n")?;
+        self.line_len = 0;
+        Ok(())
+    }
+}
+
+fn write_samples<T, W, F>(
+    mut w: W,
+    magic: (&str, &str),
+    grid: &Grid<T>,
+    maxval: Option<u16>,
+    encoding: Encoding,
+    mut samples: F,
+) -> io::Result<()>
+where
+    W: Write,
+    F: FnMut(&T, &mut Vec<u16>),
+{
+    if maxval == Some(0) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "maximum value must be at least 1"));
+    }
+    let (rows, cols) = grid.size();
+    let magic = match encoding {
+        Encoding::Ascii => magic.0,
+        Encoding::Binary => magic.1,
+    };
+    write!(w, "{}\n{} {}\n", magic, cols, rows)?;
+    if let Some(maxval) = maxval {
+        writeln!(w, "{}", maxval)?;
+    }
+    let wide = maxval.is_some_and(|maxval| maxval > 255);
+    let mut pixel: Vec<u16> = Vec::with_capacity(3);
+    let mut ascii = AsciiWriter { w: &mut w, line_len: 0 };
+    let mut raw: Vec<u8> = Vec::new();
+    for y in 0..rows {
+        for cell in grid[y].iter() {
+            pixel.clear();
+            samples(cell, &mut pixel);
+            for &sample in pixel.iter() {
+                match encoding {
+                    // Plain bitmaps need no space between their single digits
+                    Encoding::Ascii => ascii.sample(&sample.to_string(), maxval.is_some())?,
+                    Encoding::Binary if wide => raw.extend(sample.to_be_bytes()),
+                    Encoding::Binary => raw.push(sample as u8),
+                }
+            }
+        }
+        if encoding == Encoding::Ascii && cols > 0 {
+            ascii.end_line()?;
+        }
+    }
+    w.write_all(&raw)
+}
+
+/// Writes a bitmap, with `black` choosing which cells are black.
+pub fn write_pbm<T, W, F>(mut w: W, grid: &Grid<T>, encoding: Encoding, black: F) -> io::Result<()>
+where
+    W: Write,
+    F: Fn(&T) -> bool,
+{
+    if encoding == Encoding::Ascii {
+        return write_samples(w, ("P1", "P4"), grid, None, encoding, |cell, out| out.push(black(cell).into()));
+    }
+    // Raw bitmaps pack eight pixels to a byte, most significant bit first,
+    // and pad each row to a whole byte
+    let (rows, cols) = grid.size();
+    write!(w, "P4\n{} {}\n", cols, rows)?;
+    let mut raw: Vec<u8> = Vec::with_capacity(rows * cols.div_ceil(8));
+    for y in 0..rows {
+        for chunk in grid[y].chunks(8) {
+            let byte = chunk.iter()
+                .enumerate()
+                .filter(|(_, cell)| black(cell))
+                .fold(0u8, |byte, (i, _)| byte | (0x80 >> i));
+            raw.push(byte);
+        }
+    }
+    w.write_all(&raw)
+}
+
+/// Writes a graymap, with `gray` mapping each cell to a sample from 0 (black)
+/// to `maxval` (white). Fails if `maxval` is 0, which readers reject.
+pub fn write_pgm<T, W, F>(w: W, grid: &Grid<T>, maxval: u16, encoding: Encoding, gray: F) -> io::Result<()>
+where
+    W: Write,
+    F: Fn(&T) -> u16,
+{
+    write_samples(w, ("P2", "P5"), grid, Some(maxval), encoding, |cell, out| out.push(gray(cell).min(maxval)))
+}
+
+/// Writes a pixmap, with `color` mapping each cell to red, green and blue
+/// samples from 0 to `maxval`. Fails if `maxval` is 0, which readers reject.
+pub fn write_ppm<T, W, F>(w: W, grid: &Grid<T>, maxval: u16, encoding: Encoding, color: F) -> io::Result<()>
+where
+    W: Write,
+    F: Fn(&T) -> [u16; 3],
+{
+    write_samples(w, ("P3", "P6"), grid, Some(maxval), encoding, |cell, out| {
+        out.extend(color(cell).map(|sample| sample.min(maxval)))
+    })
+}
+
+/// Walks through the bytes of an image, skipping whitespace and comments
+/// between header fields and ASCII samples.
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Cursor<'_> {
+    fn skip_space(&mut self) {
+        while let Some(&b) = self.bytes.get(self.pos) {
+            if b == b'#' {
+                while self.bytes.get(self.pos).is_some_and(|&b| b != b'\n') {
+                    self.pos += 1;
+                }
+            } else if b.is_ascii_whitespace() {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Reads a decimal number, or a single digit when `digit` is set, as the
+    /// samples of a plain bitmap may be written without spaces.
+    fn number(&mut self, digit: bool) -> Option<usize> {
+        self.skip_space();
+        let start = self.pos;
+        while self.bytes.get(self.pos).is_some_and(|b| b.is_ascii_digit()) {
+            self.pos += 1;
+            if digit {
+                break;
+            }
+        }
+        std::str::from_utf8(&self.bytes[start..self.pos]).ok()?.parse().ok()
+    }
+
+    fn header_field(&mut self) -> Result<usize, ParseError> {
+        self.number(false).ok_or(ParseError::InvalidHeader)
+    }
+
+    fn sample(&mut self, digit: bool, maxval: u16) -> Result<u16, ParseError> {
+        let sample = self.number(digit).ok_or(ParseError::Truncated)?;
+        if sample > maxval as usize {
+            return Err(ParseError::SampleTooLarge { sample, maxval });
+        }
+        Ok(sample as u16)
+    }
+
+    fn raw(&mut self, len: usize) -> Result<&[u8], ParseError> {
+        let end = self.pos.checked_add(len).ok_or(ParseError::Truncated)?;
+        let bytes = self.bytes.get(self.pos..end).ok_or(ParseError::Truncated)?;
+        self.pos += len;
+        Ok(bytes)
+    }
+}
+
+fn check_sample(sample: u16, maxval: u16) -> Result<u16, ParseError> {
+    if sample > maxval {
+        return Err(ParseError::SampleTooLarge { sample: sample.into(), maxval });
+    }
+    Ok(sample)
+}
+
+/// Reads `count` samples of a graymap or pixmap.
+fn read_samples(
+    cursor: &mut Cursor,
+    count: usize,
+    maxval: u16,
+    encoding: Encoding,
+) -> Result<Vec<u16>, ParseError> {
+    match encoding {
+        Encoding::Ascii => (0..count).map(|_| cursor.sample(false, maxval)).collect(),
+        // Samples take two bytes, most significant first, once they can
+        // exceed 255
+        Encoding::Binary if maxval > 255 => cursor.raw(count.checked_mul(2).ok_or(ParseError::Truncated)?)?
+            .chunks(2)
+            .map(|pair| check_sample(u16::from_be_bytes([pair[0], pair[1]]), maxval))
+            .collect(),
+        Encoding::Binary => cursor.raw(count)?
+            .iter()
+            .map(|&sample| check_sample(sample.into(), maxval))
+            .collect(),
+    }
+}
+
+/// Reads an image in any of the six netpbm formats.
+pub fn read<R: Read>(mut reader: R) -> Result<Image, ParseError> {
+    let mut bytes: Vec<u8> = Vec::new();
+    reader.read_to_end(&mut bytes).map_err(ParseError::Io)?;
+    let magic = bytes.get(..2).ok_or(ParseError::UnknownFormat)?;
+    let (kind, encoding) = match magic {
+        b"P1" => (1, Encoding::Ascii),
+        b"P2" => (2, Encoding::Ascii),
+        b"P3" => (3, Encoding::Ascii),
+        b"P4" => (1, Encoding::Binary),
+        b"P5" => (2, Encoding::Binary),
+        b"P6" => (3, Encoding::Binary),
+        _ => return Err(ParseError::UnknownFormat),
+    };
+    let mut cursor = Cursor { bytes: &bytes, pos: 2 };
+    let cols = cursor.header_field()?;
+    let rows = cursor.header_field()?;
+    let maxval = match kind {
+        1 => 1,
+        _ => match cursor.header_field()? {
+            n @ 1..=65535 => n as u16,
+            n => return Err(ParseError::InvalidMaxval(n)),
+        },
+    };
+    if encoding == Encoding::Binary {
+        // A single whitespace byte separates the header from raw data
+        if !cursor.raw(1)?[0].is_ascii_whitespace() {
+            return Err(ParseError::InvalidHeader);
+        }
+    }
+    // Sizes no grid could hold are rejected before anything is allocated
+    let pixels = rows.checked_mul(cols).ok_or(ParseError::InvalidHeader)?;
+    match (kind, encoding) {
+        (1, Encoding::Ascii) => {
+            let cells = (0..pixels)
+                .map(|_| cursor.sample(true, 1).map(|sample| sample == 1))
+                .collect::<Result<Vec<bool>, _>>()?;
+            Ok(Image::Bitmap(Grid::new_with_cells(cells, rows, cols)))
+        }
+        (1, Encoding::Binary) => {
+            let row_bytes = cols.div_ceil(8);
+            let raw = cursor.raw(rows.checked_mul(row_bytes).ok_or(ParseError::InvalidHeader)?)?;
+            let cells = raw.chunks(row_bytes.max(1))
+                .take(rows)
+                .flat_map(|row| (0..cols).map(move |x| row[x / 8] & (0x80 >> (x % 8)) != 0))
+                .collect();
+            Ok(Image::Bitmap(Grid::new_with_cells(cells, rows, cols)))
+        }
+        (2, _) => {
+            let cells = read_samples(&mut cursor, pixels, maxval, encoding)?;
+            Ok(Image::Graymap { maxval, pixels: Grid::new_with_cells(cells, rows, cols) })
+        }
+        _ => {
+            let count = pixels.checked_mul(3).ok_or(ParseError::InvalidHeader)?;
+            let samples = read_samples(&mut cursor, count, maxval, encoding)?;
+            let cells = samples.chunks(3).map(|rgb| [rgb[0], rgb[1], rgb[2]]).collect();
+            Ok(Image::Pixmap { maxval, pixels: Grid::new_with_cells(cells, rows, cols) })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::grid::Grid;
+    use super::{read, write_pbm, write_pgm, write_ppm, Encoding, Image, ParseError};
+
+    const ENCODINGS: [Encoding; 2] = [Encoding::Ascii, Encoding::Binary];
+
+    #[test]
+    fn bitmaps_round_trip() {
+        // Eleven columns makes raw rows span two bytes with padding
+        let grid = Grid::from_fn(3, 11, |(y, x)| y * 11 + x);
+        for encoding in ENCODINGS {
+            let mut out: Vec<u8> = Vec::new();
+            write_pbm(&mut out, &grid, encoding, |&n| n % 3 == 0).unwrap();
+            let expected = grid.map(|_, &n| n % 3 == 0);
+            assert_eq!(read(&out[..]).unwrap(), Image::Bitmap(expected), "{:?}", encoding);
+        }
+        let mut out: Vec<u8> = Vec::new();
+        write_pbm(&mut out, &Grid::from_fn(2, 3, |(y, x)| y * 3 + x), Encoding::Ascii, |&n| n == 4).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "P1\n3 2\n000\n010\n");
+    }
+
+    #[test]
+    fn graymaps_round_trip() {
+        let grid = Grid::from_fn(4, 30, |(y, x)| y * 30 + x);
+        for encoding in ENCODINGS {
+            for maxval in [119, 1000] {
+                let mut out: Vec<u8> = Vec::new();
+                write_pgm(&mut out, &grid, maxval, encoding, |&n| n as u16).unwrap();
+                let expected = grid.map(|_, &n| (n as u16).min(maxval));
+                assert_eq!(read(&out[..]).unwrap(), Image::Graymap { maxval, pixels: expected });
+            }
+        }
+        let mut out: Vec<u8> = Vec::new();
+        write_pgm(&mut out, &grid, 255, Encoding::Ascii, |&n| n as u16).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.lines().all(|line| line.len() <= 70));
+    }
+
+    #[test]
+    fn pixmaps_round_trip() {
+        let grid = Grid::from_fn(2, 5, |(y, x)| y * 5 + x);
+        let color = |&n: &usize| [n as u16, 10 - n as u16, 300];
+        for encoding in ENCODINGS {
+            let mut out: Vec<u8> = Vec::new();
+            write_ppm(&mut out, &grid, 300, encoding, color).unwrap();
+            let expected = grid.map(|_, n| color(n));
+            assert_eq!(read(&out[..]).unwrap(), Image::Pixmap { maxval: 300, pixels: expected });
+        }
+    }
+
+    #[test]
+    fn reads_comments_and_rejects_bad_images() {
+        let image = read(&b"P1\n# a comment\n2 2 # size\n0 1\n10"[..]).unwrap();
+        let expected = Grid::new_with_cells(vec![false, true, true, false], 2, 2);
+        assert_eq!(image, Image::Bitmap(expected));
+        assert!(matches!(read(&b"P7\n1 1\n"[..]), Err(ParseError::UnknownFormat)));
+        assert!(matches!(read(&b"P2\n2 1\n3\n1"[..]), Err(ParseError::Truncated)));
+        assert!(matches!(read(&b"P2\n1 1\n3\n4"[..]), Err(ParseError::SampleTooLarge { sample: 4, maxval: 3 })));
+        assert!(matches!(read(&b"P5\n1 1\n0\n"[..]), Err(ParseError::InvalidMaxval(0))));
+    }
+
+    #[test]
+    fn huge_sizes_are_errors() {
+        let huge = usize::MAX / 2 + 1;
+        for magic in ["P1", "P2", "P3", "P4", "P5", "P6"] {
+            let header = format!("{}\n{} {}\n255\n", magic, huge, huge);
+            assert!(matches!(read(header.as_bytes()), Err(ParseError::InvalidHeader)), "{}", magic);
+        }
+        // Fits in a usize, but not in the file
+        assert!(matches!(read(&b"P6\n4294967296 1\n65535\n"[..]), Err(ParseError::Truncated)));
+        assert!(matches!(read(&b"P4\n8 4294967296\n "[..]), Err(ParseError::Truncated)));
+    }
+
+    #[test]
+    fn zero_maxval_is_not_written() {
+        let grid = Grid::new_with_cells(vec![0usize], 1, 1);
+        assert!(write_pgm(Vec::new(), &grid, 0, Encoding::Binary, |_| 0).is_err());
+        assert!(write_ppm(Vec::new(), &grid, 0, Encoding::Ascii, |_| [0; 3]).is_err());
+    }
+}
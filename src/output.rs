@@ -0,0 +1,160 @@
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::answer::Answer;
+use crate::days::Part;
+
+#[derive(Debug, Copy, Clone)]
+pub enum ParseFormatError {
+    UnknownFormat,
+}
+
+impl fmt::Display for ParseFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownFormat => write!(f, "expected format json, plain or tsv"),
+        }
+    }
+}
+
+impl error::Error for ParseFormatError {}
+
+/// How answers are written.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Format {
+    /// `Day 01a: answer`, for people.
+    #[default]
+    Plain,
+    /// One JSON object per line. Multi-line answers are arrays of rows.
+    Json,
+    /// Tab-separated values after a header line. Tabs, newlines and
+    /// backslashes in answers are escaped as `\t`, `\n` and `\\`.
+    Tsv,
+}
+
+impl FromStr for Format {
+    type Err = ParseFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "plain" => Ok(Self::Plain),
+            "json" => Ok(Self::Json),
+            "tsv" => Ok(Self::Tsv),
+            _ => Err(Self::Err::UnknownFormat),
+        }
+    }
+}
+
+/// The answer to one part, and how long it took to find from the input
+/// text.
+pub struct Report<'a> {
+    pub day: usize,
+    pub part: Part,
+    pub answer: &'a Answer,
+    pub elapsed: Duration,
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\t' => json.push_str("\\t"),
+            '\r' => json.push_str("\\r"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Number(n) => n.to_string(),
+        Answer::Text(s) => json_string(s),
+        Answer::Bitmap(_) => {
+            let rows: Vec<String> = answer.to_string().lines().map(json_string).collect();
+            format!("[{}]", rows.join(", "))
+        }
+    }
+}
+
+fn tsv_field(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('\t', "\\t").replace('\n', "\\n")
+}
+
+impl Format {
+    /// The line to write before any reports, if the format has one.
+    pub fn header(self) -> Option<&'static str> {
+        match self {
+            Self::Tsv => Some("day\tpart\tanswer\telapsed"),
+            _ => None,
+        }
+    }
+
+    /// Formats a report, without a trailing newline. Elapsed times are in
+    /// seconds; the plain format leaves them out.
+    pub fn report(self, report: &Report) -> String {
+        let Report { day, part, answer, elapsed } = *report;
+        match self {
+            Self::Plain if answer.is_multiline() => format!("Day {:02}{}:\n{}", day, part, answer),
+            Self::Plain => format!("Day {:02}{}: {}", day, part, answer),
+            Self::Json => format!(
+                "{{\"day\": {}, \"part\": \"{}\", \"answer\": {}, \"elapsed\": {}}}",
+                day, part, json_answer(answer), elapsed.as_secs_f64(),
+            ),
+            Self::Tsv => format!(
+                "{}\t{}\t{}\t{}",
+                day, part, tsv_field(&answer.to_string()), elapsed.as_secs_f64(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::answer::Answer;
+    use crate::days::Part;
+    use crate::grid::Grid;
+    use super::{Format, Report};
+
+    fn report(answer: &Answer) -> Report<'_> {
+        Report { day: 10, part: Part::B, answer, elapsed: Duration::from_millis(1500) }
+    }
+
+    #[test]
+    fn json() {
+        let number = Answer::Number(-13);
+        assert_eq!(
+            Format::Json.report(&report(&number)),
+            "{\"day\": 10, \"part\": \"b\", \"answer\": -13, \"elapsed\": 1.5}",
+        );
+        let text = Answer::from("say \"hi\"");
+        assert_eq!(
+            Format::Json.report(&report(&text)),
+            "{\"day\": 10, \"part\": \"b\", \"answer\": \"say \\\"hi\\\"\", \"elapsed\": 1.5}",
+        );
+        let mut bitmap: Grid<bool> = Grid::new(2, 2);
+        bitmap[0][0] = true;
+        bitmap[1][1] = true;
+        assert_eq!(
+            Format::Json.report(&report(&bitmap.into())),
+            "{\"day\": 10, \"part\": \"b\", \"answer\": [\"#.\", \".#\"], \"elapsed\": 1.5}",
+        );
+    }
+
+    #[test]
+    fn tsv_escapes_multiline_answers() {
+        let mut bitmap: Grid<bool> = Grid::new(2, 2);
+        bitmap[0][1] = true;
+        assert_eq!(Format::Tsv.report(&report(&bitmap.into())), "10\tb\t.#\\n..\t1.5");
+    }
+}
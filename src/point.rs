@@ -1,34 +1,136 @@
+use std::array;
+use std::error;
+use std::fmt;
 use std::num::ParseIntError;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+use std::slice;
 use std::str::FromStr;
 
+use crate::direction::{Heading, YAxis};
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParsePointError {
     WrongDimensions(usize),
     BadCoord(ParseIntError),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Point2D<T> {
-    pub x: T,
-    pub y: T,
+impl fmt::Display for ParsePointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongDimensions(n) => write!(f, "wrong number of coordinates: {}", n),
+            Self::BadCoord(err) => write!(f, "invalid coordinate: {}", err),
+        }
+    }
 }
 
-impl<T> Point2D<T> {
-    pub fn new(x: T, y: T) -> Self {
-        Self { x, y }
+impl error::Error for ParsePointError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::BadCoord(err) => Some(err),
+            _ => None,
+        }
     }
 }
 
-impl<T> Default for Point2D<T>
+/// A point with any number of coordinates, for when the named-field types
+/// run out of axes. Coordinates are in the order they are written, so
+/// `Point([x, y, z])`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point<T, const N: usize>(pub [T; N]);
+
+impl<T, const N: usize> Point<T, N> {
+    pub const fn new(coords: [T; N]) -> Self {
+        Self(coords)
+    }
+
+    /// Iterates over the coordinates, one per axis.
+    pub fn axes(&self) -> slice::Iter<'_, T> {
+        self.0.iter()
+    }
+
+    pub fn axes_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.0.iter_mut()
+    }
+}
+
+impl<T, const N: usize> Point<T, N>
 where
-    T: Default,
+    T: Copy + Add<Output = T> + From<i8>,
 {
+    /// Iterates over the points that differ by at most one along each axis,
+    /// `3^N - 1` of them, with the first axis changing slowest.
+    pub fn neighbours(self) -> impl Iterator<Item = Self> {
+        (0..3usize.pow(N as u32)).filter(|&i| i != 3usize.pow(N as u32) / 2).map(move |i| {
+            let mut p = self;
+            let mut digits = i;
+            for coord in p.0.iter_mut().rev() {
+                *coord = *coord + T::from((digits % 3) as i8 - 1);
+                digits /= 3;
+            }
+            p
+        })
+    }
+
+    /// Iterates over the `2N` points one step away along a single axis.
+    pub fn orthogonal_neighbours(self) -> impl Iterator<Item = Self> {
+        (0..N).flat_map(move |axis| {
+            [-1, 1].map(|d| {
+                let mut p = self;
+                p.0[axis] = p.0[axis] + T::from(d);
+                p
+            })
+        })
+    }
+}
+
+impl<T: Default, const N: usize> Default for Point<T, N> {
     fn default() -> Self {
-        Self::new(T::default(), T::default())
+        Self(std::array::from_fn(|_| T::default()))
+    }
+}
+
+impl<T, const N: usize> Index<usize> for Point<T, N> {
+    type Output = T;
+
+    fn index(&self, axis: usize) -> &T {
+        &self.0[axis]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for Point<T, N> {
+    fn index_mut(&mut self, axis: usize) -> &mut T {
+        &mut self.0[axis]
+    }
+}
+
+impl<T, const N: usize> IntoIterator for Point<T, N> {
+    type Item = T;
+    type IntoIter = array::IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
     }
 }
 
-impl<T> FromStr for Point2D<T>
+impl<T: Copy + Add<Output = T>, const N: usize> Add for Point<T, N> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(std::array::from_fn(|i| self.0[i] + other.0[i]))
+    }
+}
+
+impl<T: Copy + Sub<Output = T>, const N: usize> Sub for Point<T, N> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self(std::array::from_fn(|i| self.0[i] - other.0[i]))
+    }
+}
+
+/// Parses comma-separated coordinates, failing with the number found if
+/// there are not exactly `N`.
+impl<T, const N: usize> FromStr for Point<T, N>
 where
     T: FromStr<Err = ParseIntError>,
 {
@@ -38,16 +140,37 @@ where
         let mut ns = s
             .split(',')
             .map(|n| n.parse::<T>().map_err(Self::Err::BadCoord));
-        let x = ns.next().unwrap_or(Err(Self::Err::WrongDimensions(0)))?;
-        let y = ns.next().unwrap_or(Err(Self::Err::WrongDimensions(1)))?;
-        match ns.count() {
-            0 => Ok(Self { x, y }),
-            n => Err(Self::Err::WrongDimensions(n + 2)),
+        let mut coords = Vec::with_capacity(N);
+        for i in 0..N {
+            coords.push(ns.next().unwrap_or(Err(Self::Err::WrongDimensions(i)))?);
+        }
+        match (ns.count(), coords.try_into()) {
+            (0, Ok(coords)) => Ok(Self(coords)),
+            (n, _) => Err(Self::Err::WrongDimensions(n + N)),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Point2D<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2D<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Point2D<isize> {
+    /// Moves `n` cells in a direction, with y increasing as `axis` says.
+    pub fn step<D: Heading>(self, dir: D, axis: YAxis, n: isize) -> Self {
+        self + dir.offset(axis) * n
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct Point3D<T> {
     pub x: T,
     pub y: T,
@@ -55,36 +178,220 @@ pub struct Point3D<T> {
 }
 
 impl<T> Point3D<T> {
-    pub fn new(x: T, y: T, z: T) -> Self {
+    pub const fn new(x: T, y: T, z: T) -> Self {
         Self { x, y, z }
     }
 }
 
-impl<T> Default for Point3D<T>
-where
-    T: Default,
-{
-    fn default() -> Self {
-        Self::new(T::default(), T::default(), T::default())
-    }
+/// The signed integer operations points need that have no standard trait.
+pub trait Signed: Copy {
+    fn signum(self) -> Self;
+
+    fn abs(self) -> Self;
 }
 
-impl<T> FromStr for Point3D<T>
-where
-    T: FromStr<Err = ParseIntError>,
-{
-    type Err = ParsePointError;
+macro_rules! signed {
+    ($($t:ty)*) => {
+        $(
+            impl Signed for $t {
+                fn signum(self) -> Self {
+                    <$t>::signum(self)
+                }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut ns = s
-            .split(',')
-            .map(|n| n.parse::<T>().map_err(Self::Err::BadCoord));
-        let x = ns.next().unwrap_or(Err(Self::Err::WrongDimensions(0)))?;
-        let y = ns.next().unwrap_or(Err(Self::Err::WrongDimensions(1)))?;
-        let z = ns.next().unwrap_or(Err(Self::Err::WrongDimensions(2)))?;
-        match ns.count() {
-            0 => Ok(Self { x, y, z }),
-            n => Err(Self::Err::WrongDimensions(n + 3)),
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+            }
+        )*
+    };
+}
+
+signed! { i8 i16 i32 i64 i128 isize }
+
+/// The distance between two coordinates, which works for unsigned types too.
+fn diff<T: Copy + Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b { a - b } else { b - a }
+}
+
+/// Implements component-wise arithmetic and distances for a point type.
+macro_rules! point_ops {
+    ($point:ident { $first:ident $(, $field:ident)* }) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self { $first: self.$first + other.$first, $($field: self.$field + other.$field),* }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self { $first: self.$first - other.$first, $($field: self.$field - other.$field),* }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $first: -self.$first, $($field: -self.$field),* }
+            }
+        }
+
+        /// Scales every coordinate.
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, k: T) -> Self {
+                Self { $first: self.$first * k, $($field: self.$field * k),* }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: Self) {
+                self.$first += other.$first;
+                $(self.$field += other.$field;)*
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: Self) {
+                self.$first -= other.$first;
+                $(self.$field -= other.$field;)*
+            }
+        }
+
+        impl<T: Signed> $point<T> {
+            /// Returns the sign of each coordinate, as -1, 0 or 1. The
+            /// signum of a difference is one step towards its target.
+            pub fn signum(self) -> Self {
+                Self { $first: self.$first.signum(), $($field: self.$field.signum()),* }
+            }
+
+            pub fn abs(self) -> Self {
+                Self { $first: self.$first.abs(), $($field: self.$field.abs()),* }
+            }
         }
+
+        impl<T> $point<T>
+        where
+            T: Copy + Ord + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+        {
+            /// Returns the sum of the distances along each axis, the number
+            /// of orthogonal steps between the points.
+            pub fn manhattan(self, other: Self) -> T {
+                diff(self.$first, other.$first) $(+ diff(self.$field, other.$field))*
+            }
+
+            /// Returns the largest distance along any axis, the number of
+            /// steps between the points when diagonal steps are allowed.
+            pub fn chebyshev(self, other: Self) -> T {
+                diff(self.$first, other.$first) $(.max(diff(self.$field, other.$field)))*
+            }
+
+            /// Returns the square of the straight-line distance, which stays
+            /// an integer.
+            pub fn distance_squared(self, other: Self) -> T {
+                let d = diff(self.$first, other.$first);
+                d * d $(+ { let d = diff(self.$field, other.$field); d * d })*
+            }
+        }
+    };
+}
+
+point_ops!(Point2D { x, y });
+point_ops!(Point3D { x, y, z });
+
+/// Converts a named-field point to and from [`Point`], and parses it the
+/// same way.
+macro_rules! point_conversions {
+    ($point:ident, $n:literal { $($field:ident),* }) => {
+        impl<T> From<$point<T>> for Point<T, $n> {
+            fn from(p: $point<T>) -> Self {
+                Self([$(p.$field),*])
+            }
+        }
+
+        impl<T> From<Point<T, $n>> for $point<T> {
+            fn from(Point([$($field),*]): Point<T, $n>) -> Self {
+                Self { $($field),* }
+            }
+        }
+
+        impl<T> FromStr for $point<T>
+        where
+            T: FromStr<Err = ParseIntError>,
+        {
+            type Err = ParsePointError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                s.parse::<Point<T, $n>>().map(Self::from)
+            }
+        }
+    };
+}
+
+point_conversions!(Point2D, 2 { x, y });
+point_conversions!(Point3D, 3 { x, y, z });
+
+#[cfg(test)]
+mod test {
+    use super::{ParsePointError, Point, Point2D, Point3D};
+
+    #[test]
+    fn arithmetic() {
+        let mut p = Point2D::new(3, -2);
+        let q = Point2D::new(-1, 5);
+        assert_eq!(p + q, Point2D::new(2, 3));
+        assert_eq!(p - q, Point2D::new(4, -7));
+        assert_eq!(-p, Point2D::new(-3, 2));
+        assert_eq!(p * 3, Point2D::new(9, -6));
+        assert_eq!((p - q).signum(), Point2D::new(1, -1));
+        assert_eq!(q.abs(), Point2D::new(1, 5));
+        p += q;
+        p -= Point2D::new(2, 0);
+        assert_eq!(p, Point2D::new(0, 3));
+        assert_eq!(Point3D::new(1, 2, 3) + Point3D::new(1, 1, 1), Point3D::new(2, 3, 4));
+    }
+
+    #[test]
+    fn distances() {
+        let p = Point2D::new(3, -2);
+        let q = Point2D::new(-1, 5);
+        assert_eq!(p.manhattan(q), 11);
+        assert_eq!(p.chebyshev(q), 7);
+        assert_eq!(p.distance_squared(q), 65);
+        let a: Point3D<usize> = Point3D::new(1, 8, 2);
+        let b = Point3D::new(4, 6, 2);
+        assert_eq!((a.manhattan(b), b.manhattan(a)), (5, 5));
+        assert_eq!(a.chebyshev(b), 3);
+        assert_eq!(a.distance_squared(b), 13);
+    }
+
+    #[test]
+    fn parse_checks_dimensions() {
+        assert_eq!("3,-4".parse(), Ok(Point2D::new(3, -4)));
+        assert_eq!("1,2,3,4".parse(), Ok(Point([1, 2, 3, 4])));
+        assert_eq!("1,2".parse::<Point3D<i32>>(), Err(ParsePointError::WrongDimensions(2)));
+        assert_eq!("1,2,3".parse::<Point2D<i32>>(), Err(ParsePointError::WrongDimensions(3)));
+        assert!(matches!("1,x".parse::<Point<i32, 2>>(), Err(ParsePointError::BadCoord(_))));
+    }
+
+    #[test]
+    fn n_dimensional_points() {
+        let p = Point::from(Point3D::new(1, 2, 3));
+        assert_eq!(p.axes().sum::<i32>(), 6);
+        assert_eq!(Point3D::from(p + Point([1, 1, 1])), Point3D::new(2, 3, 4));
+        assert_eq!(Point2D::from(Point::<u8, 2>::default()), Point2D::new(0, 0));
+        let origin: Point<i64, 4> = Point::default();
+        assert_eq!(origin.neighbours().count(), 80);
+        assert!(origin.neighbours().all(|q| q != origin && q.axes().all(|c| c.abs() <= 1)));
+        let around: Vec<_> = Point([5, 5]).neighbours().map(|q| (q[0], q[1])).collect();
+        assert_eq!(around, vec![(4, 4), (4, 5), (4, 6), (5, 4), (5, 6), (6, 4), (6, 5), (6, 6)]);
+        let orthogonal: Vec<_> = Point([0, 0, 0]).orthogonal_neighbours().collect();
+        assert_eq!(orthogonal.len(), 6);
+        assert_eq!(orthogonal[5], Point([0, 0, 1]));
     }
 }
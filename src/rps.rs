@@ -1,3 +1,5 @@
+use std::error;
+use std::fmt;
 use std::str::FromStr;
 
 #[derive(Debug, Copy, Clone)]
@@ -6,6 +8,17 @@ pub enum ParseError {
     InsufficientCharacters,
 }
 
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnrecognizedCharacter => write!(f, "unrecognized play"),
+            Self::InsufficientCharacters => write!(f, "expected two plays separated by whitespace"),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
 #[derive(Debug, Copy, Clone)]
 pub enum Play {
     Rock, Paper, Scissors
@@ -26,9 +26,9 @@ impl Rucksack {
     }
 
     pub fn priority(c: char) -> usize {
-        if ('A'..='Z').contains(&c) {
+        if c.is_ascii_uppercase() {
             (c as usize) - ('A' as usize) + 27
-        } else if ('a'..='z').contains(&c) {
+        } else if c.is_ascii_lowercase() {
             (c as usize) - ('a' as usize) + 1
         } else {
             0
@@ -0,0 +1,110 @@
+//! Line-of-sight queries over grids of heights, such as the trees on day 08.
+//! A cell blocks the view of any cell no taller than itself.
+
+use crate::bitgrid::BitGrid;
+use crate::grid::{Grid, OFFSETS4};
+
+/// For every cell, how far it can see in `direction` and whether a cell at
+/// least as tall stops the view before the edge.
+///
+/// Each line of cells in `direction` is walked once from its far end with a
+/// stack of the cells that could still block a view, so the whole grid takes
+/// O(rows * cols) however far the views reach.
+fn sight_lines<T: Ord>(grid: &Grid<T>, direction: (isize, isize)) -> Grid<(usize, bool)> {
+    let (rows, cols) = grid.size();
+    let mut sight: Grid<(usize, bool)> = Grid::new(rows, cols);
+    if rows == 0 || cols == 0 {
+        return sight;
+    }
+    let back = (-direction.0, -direction.1);
+    let mut stack: Vec<(&T, usize)> = Vec::new();
+    for (y, x, _) in grid.iter() {
+        // Only start from the last cell of each line
+        if grid.offset((y, x), direction).is_some() {
+            continue;
+        }
+        stack.clear();
+        let line = std::iter::once(((y, x), &grid[(y, x)])).chain(grid.ray((y, x), back));
+        for (i, (pos, height)) in line.enumerate() {
+            while stack.last().is_some_and(|&(top, _)| top < height) {
+                stack.pop();
+            }
+            sight[pos] = match stack.last() {
+                Some(&(_, j)) => (i - j, true),
+                None => (i, false),
+            };
+            stack.push((height, i));
+        }
+    }
+    sight
+}
+
+/// Marks the cells that can be seen from outside the grid, looking along a
+/// row or a column.
+pub fn visibility_map<T: Ord>(grid: &Grid<T>) -> BitGrid {
+    let (rows, cols) = grid.size();
+    let mut visible = BitGrid::new(rows, cols);
+    for direction in OFFSETS4 {
+        for (y, x, &(_, blocked)) in sight_lines(grid, direction).iter() {
+            if !blocked {
+                visible.set((y, x), true);
+            }
+        }
+    }
+    visible
+}
+
+/// Returns how many cells each cell can see in `direction` as `(dy, dx)`,
+/// counting the cell that blocks the view but nothing beyond it.
+pub fn viewing_distance<T: Ord>(grid: &Grid<T>, direction: (isize, isize)) -> Grid<usize> {
+    sight_lines(grid, direction).map(|_, &(distance, _)| distance)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::grid::{Grid, OFFSETS4};
+    use crate::input::try_parse_uint_grid;
+    use super::{viewing_distance, visibility_map};
+
+    const TREES: &str = "30373\n25512\n65332\n33549\n35390\n";
+
+    /// Looks along every ray one cell at a time, to check the stack-based
+    /// versions against.
+    fn naive_distance(grid: &Grid<usize>, pos: (usize, usize), direction: (isize, isize)) -> (usize, bool) {
+        let height = grid[pos];
+        let mut distance = 0;
+        for (_, &other) in grid.ray(pos, direction) {
+            distance += 1;
+            if other >= height {
+                return (distance, true);
+            }
+        }
+        (distance, false)
+    }
+
+    #[test]
+    fn matches_looking_along_each_ray() {
+        let grid = try_parse_uint_grid(TREES).unwrap();
+        let visible = visibility_map(&grid);
+        for direction in OFFSETS4.into_iter().chain([(1, 1), (-1, 1)]) {
+            let distances = viewing_distance(&grid, direction);
+            for (y, x, &distance) in distances.iter() {
+                assert_eq!(distance, naive_distance(&grid, (y, x), direction).0, "{:?} at {:?}", direction, (y, x));
+            }
+        }
+        for (y, x, seen) in visible.iter() {
+            let expected = OFFSETS4.iter().any(|&direction| !naive_distance(&grid, (y, x), direction).1);
+            assert_eq!(seen, expected, "at {:?}", (y, x));
+        }
+        assert_eq!(visible.count_ones(), 21);
+    }
+
+    #[test]
+    fn empty_grids_have_no_sight_lines() {
+        for (rows, cols) in [(0, 0), (2, 0), (0, 3)] {
+            let grid: Grid<usize> = Grid::new(rows, cols);
+            assert_eq!(visibility_map(&grid).count_ones(), 0);
+            assert_eq!(viewing_distance(&grid, (0, 1)).size(), (rows, cols));
+        }
+    }
+}
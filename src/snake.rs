@@ -1,7 +1,10 @@
-use std::collections::HashSet;
+use std::error;
+use std::fmt;
 use std::str::FromStr;
 
+use crate::direction::{Direction4, YAxis};
 use crate::point::Point2D;
+use crate::sparse::SparseGrid;
 
 #[derive(Debug, Copy, Clone)]
 pub enum ParseMovementError {
@@ -9,12 +12,23 @@ pub enum ParseMovementError {
     InvalidDistance,
 }
 
+impl fmt::Display for ParseMovementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidDirection => {
+                write!(f, "expected a direction of L, R, U or D, a compass point or an arrow")
+            }
+            Self::InvalidDistance => write!(f, "expected a distance"),
+        }
+    }
+}
+
+impl error::Error for ParseMovementError {}
+
 #[derive(Debug, Copy, Clone)]
-pub enum Movement {
-    Left(usize),
-    Right(usize),
-    Up(usize),
-    Down(usize),
+pub struct Movement {
+    pub direction: Direction4,
+    pub distance: usize,
 }
 
 impl FromStr for Movement {
@@ -23,24 +37,19 @@ impl FromStr for Movement {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut parts = s.split_whitespace();
         let dir = parts.next().ok_or(Self::Err::InvalidDirection)?;
+        let direction = dir.parse().map_err(|_| Self::Err::InvalidDirection)?;
 
         let dist = parts.next().ok_or(Self::Err::InvalidDistance)?;
-        let dist = dist.parse::<usize>().map_err(|_| Self::Err::InvalidDistance)?;
-        
-        match dir {
-            "L" => Ok(Movement::Left(dist)),
-            "R" => Ok(Movement::Right(dist)),
-            "U" => Ok(Movement::Up(dist)),
-            "D" => Ok(Movement::Down(dist)),
-            _ => Err(Self::Err::InvalidDirection),
-        }
+        let distance = dist.parse::<usize>().map_err(|_| Self::Err::InvalidDistance)?;
+
+        Ok(Movement { direction, distance })
     }
 }
 
 pub struct Snake {
     pub head: Point2D<isize>,
     pub tails: Vec<Point2D<isize>>,
-    pub tails_visited: Vec<HashSet<Point2D<isize>>>,
+    pub tails_visited: Vec<SparseGrid<()>>,
 }
 
 impl Snake {
@@ -49,41 +58,38 @@ impl Snake {
         let mut tails_visited = Vec::new();
         (0..num_tails).for_each(|_| {
             tails.push(head);
-            let mut visited = HashSet::new();
-            visited.insert(head);
+            let mut visited = SparseGrid::new();
+            visited.insert(head, ());
             tails_visited.push(visited);
         });
         Self { head, tails, tails_visited }
     }
 
-    pub fn move_left(&mut self) {
-        self.head.x -= 1;
+    /// Moves the head one cell, with up as +y, and lets the tails follow.
+    pub fn move_head(&mut self, dir: Direction4) {
+        self.head = self.head.step(dir, YAxis::Up, 1);
         self.update_tails();
     }
 
+    pub fn move_left(&mut self) {
+        self.move_head(Direction4::Left);
+    }
+
     pub fn move_right(&mut self) {
-        self.head.x += 1;
-        self.update_tails();
+        self.move_head(Direction4::Right);
     }
 
     pub fn move_up(&mut self) {
-        self.head.y += 1;
-        self.update_tails();
+        self.move_head(Direction4::Up);
     }
 
     pub fn move_down(&mut self) {
-        self.head.y -= 1;
-        self.update_tails();
+        self.move_head(Direction4::Down);
     }
 
     pub fn apply_moves(&mut self, moves: &[Movement]) {
         for mv in moves {
-            match mv {
-                Movement::Left(x) => (0..*x).for_each(|_| self.move_left()),
-                Movement::Right(x) => (0..*x).for_each(|_| self.move_right()),
-                Movement::Up(y) => (0..*y).for_each(|_| self.move_up()),
-                Movement::Down(y) => (0..*y).for_each(|_| self.move_down()),
-            }
+            (0..mv.distance).for_each(|_| self.move_head(mv.direction));
         }
     }
 
@@ -92,20 +98,13 @@ impl Snake {
     }
 
     fn update_tail(&mut self, index: usize) {
-        let head = if index > 0 { &self.tails[index - 1] } else { &self.head };
-        let tail = &self.tails[index];
-        let dist = Point2D::new(head.x - tail.x, head.y - tail.y);
-        let adx = dist.x.abs();
-        let ady = dist.y.abs();
-        if adx > 1 || ady > 1 {
-            // On a diagonal separation, both coordinates change
-            let threshold = if adx > 0 && ady > 0 { 0 } else { 1 };
-            let dx = if dist.x > threshold { 1 } else if dist.x < -threshold { -1 } else { 0 };
-            let dy = if dist.y > threshold { 1 } else if dist.y < -threshold { -1 } else { 0 };
-            self.tails[index].x += dx;
-            self.tails[index].y += dy;
+        let head = if index > 0 { self.tails[index - 1] } else { self.head };
+        let tail = self.tails[index];
+        if head.chebyshev(tail) > 1 {
+            // Each coordinate closes one step, so a diagonal gap closes diagonally
+            self.tails[index] += (head - tail).signum();
         }
-        self.tails_visited[index].insert(self.tails[index]);
+        self.tails_visited[index].insert(self.tails[index], ());
     }
 }
 
@@ -117,8 +116,17 @@ impl Default for Snake {
 
 #[cfg(test)]
 mod test {
+    use crate::direction::Direction4;
     use crate::point::Point2D;
-    use super::Snake;
+    use super::{Movement, Snake};
+
+    #[test]
+    fn parse_movements() {
+        let movement: Movement = "N 3".parse().unwrap();
+        assert_eq!((movement.direction, movement.distance), (Direction4::Up, 3));
+        let err = "X 1".parse::<Movement>().unwrap_err();
+        assert_eq!(err.to_string(), "expected a direction of L, R, U or D, a compass point or an arrow");
+    }
 
     #[test]
     fn separated_left() {
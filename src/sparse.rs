@@ -0,0 +1,212 @@
+use std::collections::hash_map::{self, HashMap};
+
+use crate::grid::Grid;
+use crate::point::Point2D;
+
+pub type Point = Point2D<isize>;
+
+/// An inclusive rectangle of points.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    pub fn new(min: Point, max: Point) -> Self {
+        Self { min, max }
+    }
+
+    /// The bounds holding a single point.
+    pub fn at(p: Point) -> Self {
+        Self::new(p, p)
+    }
+
+    /// Grows the bounds, if needed, so that they hold `p`.
+    pub fn include(&mut self, p: Point) {
+        self.min.x = self.min.x.min(p.x);
+        self.min.y = self.min.y.min(p.y);
+        self.max.x = self.max.x.max(p.x);
+        self.max.y = self.max.y.max(p.y);
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    /// Returns the number of rows and columns the bounds cover, in the same
+    /// order as [`Grid::size`], or None if a side spans every `isize` and so
+    /// cannot be counted in a `usize`.
+    pub fn size(&self) -> Option<(usize, usize)> {
+        let rows = self.max.y.abs_diff(self.min.y).checked_add(1)?;
+        let cols = self.max.x.abs_diff(self.min.x).checked_add(1)?;
+        Some((rows, cols))
+    }
+}
+
+/// A grid that stores only the cells that have been set, so it can cover
+/// any area of signed coordinates. Rows run along x and y increases from one
+/// row to the next, as for [`Grid`].
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    bounds: Option<Bounds>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    /// Sets a cell, returning its previous value. The bounds grow to hold
+    /// it.
+    pub fn insert(&mut self, p: Point, value: T) -> Option<T> {
+        match self.bounds.as_mut() {
+            Some(bounds) => bounds.include(p),
+            None => self.bounds = Some(Bounds::at(p)),
+        }
+        self.cells.insert(p, value)
+    }
+
+    /// Clears a cell. The bounds never shrink, so they may still include it.
+    pub fn remove(&mut self, p: Point) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    /// Returns the number of cells that are set.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Returns the smallest rectangle holding every cell ever set, or None
+    /// if nothing has been.
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    /// Iterates over the cells that are set, in no particular order.
+    pub fn iter(&self) -> hash_map::Iter<'_, Point, T> {
+        self.cells.iter()
+    }
+
+    /// Draws the bounds as text, one line per row, choosing a character for
+    /// each cell whether or not it is set. An empty grid renders as an
+    /// empty string.
+    pub fn render<F>(&self, mut f: F) -> String
+    where
+        F: FnMut(Option<&T>) -> char,
+    {
+        let Some(bounds) = self.bounds else {
+            return String::new();
+        };
+        let rows: Vec<String> = (bounds.min.y..=bounds.max.y)
+            .map(|y| (bounds.min.x..=bounds.max.x).map(|x| f(self.get(Point::new(x, y)))).collect())
+            .collect();
+        rows.join("\n")
+    }
+}
+
+impl<T: Clone + Default> SparseGrid<T> {
+    /// Copies the cells within `bounds` into a dense grid, whose origin is
+    /// `bounds.min`. Unset cells take the default value and cells outside
+    /// the bounds are left out. Panics if the bounds are too large for a
+    /// grid to hold.
+    pub fn to_grid_within(&self, bounds: Bounds) -> Grid<T> {
+        let (rows, cols) = bounds.size().expect("bounds are too large for a grid");
+        let mut grid: Grid<T> = Grid::new(rows, cols);
+        grid.set_origin(bounds.min);
+        for (&p, value) in self.cells.iter().filter(|(&p, _)| bounds.contains(p)) {
+            let y = p.y.abs_diff(bounds.min.y);
+            let x = p.x.abs_diff(bounds.min.x);
+            grid[(y, x)] = value.clone();
+        }
+        grid
+    }
+
+    /// Copies every cell into a dense grid covering [`Self::bounds`].
+    pub fn to_grid(&self) -> Grid<T> {
+        match self.bounds {
+            Some(bounds) => self.to_grid_within(bounds),
+            None => Grid::new(0, 0),
+        }
+    }
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Extend<(Point, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point, T)>>(&mut self, iter: I) {
+        for (p, value) in iter {
+            self.insert(p, value);
+        }
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Bounds, Point, SparseGrid};
+
+    #[test]
+    fn bounds_grow_on_insert() {
+        let mut grid: SparseGrid<char> = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        grid.insert(Point::new(2, -1), 'a');
+        assert_eq!(grid.bounds(), Some(Bounds::at(Point::new(2, -1))));
+        grid.insert(Point::new(-3, 4), 'b');
+        assert_eq!(grid.insert(Point::new(2, -1), 'c'), Some('a'));
+        let bounds = grid.bounds().unwrap();
+        assert_eq!(bounds, Bounds::new(Point::new(-3, -1), Point::new(2, 4)));
+        assert_eq!(bounds.size(), Some((6, 6)));
+        let everything = Bounds::new(Point::new(0, isize::MIN), Point::new(0, isize::MAX));
+        assert_eq!(everything.size(), None);
+        assert_eq!(Bounds::new(Point::new(0, 0), Point::new(isize::MAX, 0)).size(), Some((1, isize::MAX as usize + 1)));
+        assert_eq!(grid.len(), 2);
+    }
+
+    #[test]
+    fn render_and_densify() {
+        let grid: SparseGrid<char> = [(Point::new(-1, 0), 'a'), (Point::new(1, 1), 'b')]
+            .into_iter()
+            .collect();
+        assert_eq!(grid.render(|c| c.copied().unwrap_or('.')), "a..\n..b");
+        let dense = grid.to_grid();
+        assert_eq!(dense.size(), (2, 3));
+        assert_eq!((dense[(0, 0)], dense[(1, 2)], dense[(0, 1)]), ('a', 'b', '\0'));
+        let window = grid.to_grid_within(Bounds::new(Point::new(0, 0), Point::new(1, 1)));
+        assert_eq!(window.size(), (2, 2));
+        assert_eq!(window[(1, 1)], 'b');
+        assert_eq!(dense.to_local(Point::new(1, 1)), Some((1, 2)));
+        assert_eq!(SparseGrid::<char>::new().render(|_| '#'), "");
+    }
+}
@@ -0,0 +1,104 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc::days::{self, Part};
+
+/// Answers for the real puzzle inputs. Each person's inputs differ, so this
+/// file is not checked in; create it in the same format as `samples.txt` to
+/// check your own answers.
+const LOCAL_ANSWERS: &str = "answers.txt";
+
+struct Case {
+    line: usize,
+    day: usize,
+    part: Part,
+    input: String,
+    expected: String,
+}
+
+fn root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn load(path: &Path) -> Vec<Case> {
+    let text = fs::read_to_string(path)
+        .unwrap_or_else(|err| panic!("{}: {}", path.display(), err));
+    let mut cases: Vec<Case> = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(row) = line.strip_prefix("    ") {
+            let case = cases.last_mut()
+                .unwrap_or_else(|| panic!("{}:{}: continuation without an entry", path.display(), i + 1));
+            if !case.expected.is_empty() {
+                case.expected.push('\n');
+            }
+            case.expected.push_str(row);
+            continue;
+        }
+        let mut fields = line.splitn(4, ' ');
+        let mut field = |name: &str| {
+            fields.next()
+                .unwrap_or_else(|| panic!("{}:{}: missing {}", path.display(), i + 1, name))
+        };
+        let day = field("day").parse()
+            .unwrap_or_else(|_| panic!("{}:{}: invalid day", path.display(), i + 1));
+        let part = field("part").parse()
+            .unwrap_or_else(|_| panic!("{}:{}: invalid part", path.display(), i + 1));
+        let input = field("input").to_owned();
+        let expected = fields.next().unwrap_or("").to_owned();
+        cases.push(Case { line: i + 1, day, part, input, expected });
+    }
+    cases
+}
+
+/// Runs every case and returns a description of each one that failed, so a
+/// single run reports all regressions rather than just the first.
+fn check(path: &Path) -> Vec<String> {
+    let mut failures = Vec::new();
+    for case in load(path) {
+        let label = format!("line {}: day {:02}{} on {}", case.line, case.day, case.part, case.input);
+        let input = match fs::read_to_string(root().join(&case.input)) {
+            Ok(input) => input,
+            Err(err) => {
+                failures.push(format!("{}: {}", label, err));
+                continue;
+            }
+        };
+        match days::solve(case.day, case.part, &input) {
+            Ok(answer) if answer.to_string() == case.expected => {}
+            Ok(answer) => failures.push(format!("{}: expected\n{}\nbut got\n{}", label, case.expected, answer)),
+            Err(err) => failures.push(format!("{}: {}", label, err)),
+        }
+    }
+    failures
+}
+
+#[test]
+fn samples() {
+    let failures = check(&root().join("tests").join("samples.txt"));
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn real_inputs() {
+    let path = root().join(LOCAL_ANSWERS);
+    if !path.exists() {
+        eprintln!("{} not found, skipping real input answers", path.display());
+        return;
+    }
+    let failures = check(&path);
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn every_solution_has_a_sample() {
+    let cases = load(&root().join("tests").join("samples.txt"));
+    for solution in days::SOLUTIONS {
+        assert!(
+            cases.iter().any(|case| case.day == solution.day && case.part == solution.part),
+            "no sample answer for day {:02}{}", solution.day, solution.part,
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use aoc::days::{self, Part};
+
+/// Solves a day on input that is valid but has nothing in it.
+fn answer(day: usize, part: Part, input: &str) -> String {
+    days::solve(day, part, input).unwrap_or_else(|err| panic!("day {:02}{}: {}", day, part, err)).to_string()
+}
+
+/// Solves a day on malformed input, which should fail with an error rather
+/// than panic, and returns the error text.
+fn error(day: usize, part: Part, input: &str) -> String {
+    match days::solve(day, part, input) {
+        Ok(answer) => panic!("day {:02}{} accepted malformed input, answering {}", day, part, answer),
+        Err(err) => err.to_string(),
+    }
+}
+
+#[test]
+fn day05_moves_to_missing_stacks() {
+    let input = "[A]\n 1 \n\nmove 1 from 1 to 1\nmove 1 from 3 to 1\n";
+    assert_eq!(error(5, Part::A, input), "line 5: there is no stack 3");
+    assert_eq!(error(5, Part::B, "[A]\n 1 \n\nmove 1 from 1 to 0\n"), "line 4: there is no stack 0");
+}
+
+#[test]
+fn day12_without_an_end() {
+    assert_eq!(error(12, Part::A, "Sab\n"), "end position not found");
+    assert_eq!(error(12, Part::B, "Sab\n"), "end position not found");
+    assert_eq!(error(12, Part::A, "abE\n"), "start position not found");
+}
+
+#[test]
+fn day08_without_trees() {
+    assert_eq!(answer(8, Part::A, "\n"), "0");
+    assert_eq!(answer(8, Part::B, "\n"), "0");
+}